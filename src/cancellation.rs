@@ -0,0 +1,94 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Returned by work that stopped because it was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+pub type Cancellable<T> = Result<T, Cancelled>;
+
+/// Shared flag that long-running work checks between steps, so a request
+/// the client cancelled, or that ran out of time, stops early instead of
+/// finishing work nobody will read.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    /// A token is also cancelled when its parent is.
+    parent: Option<Box<CancellationToken>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token that is cancelled on its own or together with this one.
+    pub fn child(&self) -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            parent: Some(Box::new(self.clone())),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.is_cancelled())
+    }
+
+    /// Returns `Err(Cancelled)` once the token is cancelled, for use with `?`
+    /// inside analysis loops.
+    pub fn check(&self) -> Cancellable<()> {
+        if self.is_cancelled() {
+            return Err(Cancelled);
+        }
+        Ok(())
+    }
+
+    /// A guard that cancels the token when dropped. tower-lsp drops the
+    /// future of a request the client cancels, so holding the guard in that
+    /// future turns `$/cancelRequest` into a cancelled token.
+    pub fn drop_guard(&self) -> DropGuard {
+        DropGuard {
+            token: self.clone(),
+        }
+    }
+}
+
+pub struct DropGuard {
+    token: CancellationToken,
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
+#[test]
+fn test_drop_guard_cancels_token() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    assert_eq!(clone.check(), Ok(()));
+    drop(token.drop_guard());
+    assert!(clone.is_cancelled());
+    assert_eq!(clone.check(), Err(Cancelled));
+}
+
+#[test]
+fn test_child_is_cancelled_with_parent() {
+    let parent = CancellationToken::new();
+    let child = parent.child();
+    child.child().cancel();
+    assert!(!child.is_cancelled());
+    parent.cancel();
+    assert!(child.is_cancelled());
+}
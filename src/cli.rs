@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use serde_json::{json, Value};
+use tower_lsp::lsp_types::{TextDocumentItem, Url};
+use tree_sitter::Node;
+
+use crate::{
+    index::symbols::get_file_symbols,
+    logging::LogSettings,
+    parser::parse,
+    text::Encoding,
+    utils::{read_document, MyResult},
+};
+
+pub fn build_cli() -> Command {
+    Command::new("pols")
+        .about("Language server for PowerOn specfiles")
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("log-level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .global(true)
+                .help("Log level: off, error, warn, info, debug or trace [env: POLS_LOG_LEVEL]"),
+        )
+        .arg(
+            Arg::new("log-file")
+                .long("log-file")
+                .value_name("PATH")
+                .global(true)
+                .help("Log to this file instead of stderr [env: POLS_LOG_FILE]"),
+        )
+        .arg(
+            Arg::new("log-config")
+                .long("log-config")
+                .value_name("PATH")
+                .global(true)
+                .help(
+                    "log4rs configuration file, overriding the other log options \
+                     [env: POLS_LOG_CONFIG]",
+                ),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Run the language server (the default when no command is given)")
+                .arg(
+                    Arg::new("stdio")
+                        .long("stdio")
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with("listen")
+                        .help("Talk to the client over stdin and stdout (the default)"),
+                )
+                .arg(
+                    Arg::new("listen")
+                        .long("listen")
+                        .value_name("PORT")
+                        .value_parser(value_parser!(u16))
+                        .help("Accept clients on this TCP port of 127.0.0.1"),
+                ),
+        )
+        .subcommand(
+            Command::new("parse")
+                .about("Print the syntax tree of a specfile")
+                .arg(Arg::new("file").required(true).value_name("FILE"))
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_parser(["sexp", "json"])
+                        .default_value("sexp")
+                        .help("Output format"),
+                ),
+        )
+        .subcommand(
+            Command::new("symbols")
+                .about("Print the variables and procedures a specfile declares")
+                .arg(Arg::new("file").required(true).value_name("FILE")),
+        )
+}
+
+/// The log settings from the environment, overridden by `matches`.
+pub fn get_log_settings(matches: &ArgMatches) -> LogSettings {
+    let mut settings = LogSettings::from_env();
+    if let Some(level) = matches.get_one::<String>("log-level") {
+        settings.set_level(level);
+    }
+    if let Some(file) = matches.get_one::<String>("log-file") {
+        settings.file = Some(PathBuf::from(file));
+    }
+    if let Some(config) = matches.get_one::<String>("log-config") {
+        settings.config = Some(PathBuf::from(config));
+    }
+    settings
+}
+
+/// `pols parse`: the syntax tree of `file` as an s-expression or as JSON.
+pub fn run_parse(file: &Path, format: &str) -> MyResult<String> {
+    let document = read_file(file)?;
+    let tree = parse(&document.text).ok_or("Error parsing file")?;
+    let root = tree.root_node();
+    match format {
+        "json" => Ok(serde_json::to_string_pretty(&node_to_json(
+            root,
+            document.text.as_bytes(),
+        ))?),
+        _ => Ok(root.to_sexp()),
+    }
+}
+
+/// `pols symbols`: one line per declaration, with its 1-based position.
+pub fn run_symbols(file: &Path) -> MyResult<String> {
+    let document = read_file(file)?;
+    let tree = parse(&document.text).ok_or("Error parsing file")?;
+    let symbols = get_file_symbols(&document, &tree);
+
+    let mut lines: Vec<String> = Vec::new();
+    for variable in &symbols.variables {
+        lines.push(format!(
+            "{}:{}\tvariable\t{}={}",
+            variable.range.start.line + 1,
+            variable.range.start.character + 1,
+            variable.name,
+            variable.type_description()
+        ));
+    }
+    for procedure in &symbols.procedures {
+        lines.push(format!(
+            "{}:{}\tprocedure\t{}",
+            procedure.range.start.line + 1,
+            procedure.range.start.character + 1,
+            procedure.name
+        ));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn read_file(file: &Path) -> MyResult<TextDocumentItem> {
+    let path = file
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", file.display(), e))?;
+    let url =
+        Url::from_file_path(&path).map_err(|_| format!("{} is not a file", file.display()))?;
+    read_document(&url, Encoding::Auto)
+}
+
+/// A node and its named children. Leaves carry their source text.
+fn node_to_json(node: Node, source: &[u8]) -> Value {
+    let position = |point: tree_sitter::Point| json!({ "row": point.row, "column": point.column });
+    let mut value = json!({
+        "kind": node.kind(),
+        "start": position(node.start_position()),
+        "end": position(node.end_position()),
+    });
+    if node.is_error() || node.is_missing() {
+        value["error"] = Value::Bool(true);
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Value> = node
+        .named_children(&mut cursor)
+        .map(|child| node_to_json(child, source))
+        .collect();
+    if children.is_empty() {
+        value["text"] = Value::String(node.utf8_text(source).unwrap_or_default().to_string());
+    } else {
+        value["children"] = Value::Array(children);
+    }
+    value
+}
+
+#[test]
+fn test_log_flags() {
+    let matches = build_cli().get_matches_from([
+        "pols",
+        "serve",
+        "--log-level",
+        "debug",
+        "--log-file",
+        "pols.log",
+    ]);
+    let settings = get_log_settings(&matches);
+    assert_eq!(settings.level, log::LevelFilter::Debug);
+    assert_eq!(settings.file, Some(PathBuf::from("pols.log")));
+}
+
+#[test]
+fn test_parse_and_symbols() {
+    let file = std::env::temp_dir().join(format!("pols-cli-test-{}.DEF", std::process::id()));
+    std::fs::write(&file, "TRUE=1\nNAME=CHARACTER(40)\n").unwrap();
+
+    let sexp = run_parse(&file, "sexp").unwrap();
+    assert!(sexp.starts_with("(source_file"), "{}", sexp);
+    let json: Value = serde_json::from_str(&run_parse(&file, "json").unwrap()).unwrap();
+    assert_eq!(json["kind"], "source_file");
+    assert_eq!(
+        run_symbols(&file).unwrap(),
+        "1:1\tvariable\tTRUE=NUMBER\n2:1\tvariable\tNAME=CHARACTER(40)"
+    );
+
+    std::fs::remove_file(file).unwrap();
+}
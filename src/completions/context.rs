@@ -0,0 +1,452 @@
+use tree_sitter::{Node, Point, Tree};
+
+use crate::{
+    database::{
+        functions::BUILTIN_FUNCTIONS,
+        record_types::{is_record_type, normalize_record_type},
+    },
+    text::point_to_byte,
+};
+
+/// What the code at the cursor expects next, which decides what completion
+/// offers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompletionContext {
+    /// After `TARGET=`, naming the record the specfile runs on.
+    TargetRecord,
+    /// After `RECORD:`, naming a field of the record.
+    Field(String),
+    /// In a `FOR EACH` header or after `ANY`, naming the record to loop over.
+    LoopRecord,
+    /// After `WITH` in a `FOR` header or `ANY` expression. The condition
+    /// usually reads fields of the records named before `WITH`.
+    WithCondition(Vec<String>),
+    /// After `@`, naming a system variable.
+    SystemVariable,
+    /// Inside the argument list of the named builtin function.
+    FunctionArgument(String),
+    /// After `=` in a DEFINE division, naming the declared type.
+    DeclarationType,
+    /// Naming a new variable in a DEFINE division, where nothing is offered.
+    DeclarationName,
+    /// After `CALL`, naming a procedure.
+    ProcedureName,
+    /// At the start of a statement.
+    Statement,
+    /// Anywhere else an expression may go.
+    Expression,
+}
+
+/// The context at the cursor and the part of the word before the cursor that
+/// has already been typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionTarget {
+    pub context: CompletionContext,
+    pub prefix: String,
+    /// For a field, what it is assigned to or compared with, whose type the
+    /// field is expected to have.
+    pub operand: Option<Operand>,
+}
+
+/// The other side of an assignment or comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operand {
+    Variable(String),
+    /// A record and one of its fields.
+    Field(String, String),
+}
+
+/// How many tokens before the cursor are looked at. A builtin's argument
+/// list rarely holds more.
+const MAX_TOKENS: usize = 32;
+
+/// Keywords that start a division or close one. The DEFINE division holds no
+/// blocks, so the first of these before the cursor tells whether the cursor
+/// is inside it.
+const DIVISION_KEYWORDS: &[&str] = &[
+    "target",
+    "define",
+    "setup",
+    "select",
+    "sort",
+    "print",
+    "total",
+    "procedure",
+    "end",
+];
+
+/// Works out the completion context from the tokens of `tree` before
+/// `point`. Code being typed rarely parses cleanly, and an unfinished
+/// declaration can leave the DEFINE division open to the end of the file, so
+/// the context comes from the tokens tree-sitter recovered rather than from
+/// the shape of the tree.
+pub fn completion_target(tree: &Tree, text: &str, point: Point) -> CompletionTarget {
+    let byte = point_to_byte(text, point);
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut division: Option<String> = None;
+    walk_tokens_back(tree.root_node(), text, byte, point.row, |token| {
+        if division.is_none() {
+            let word = token.text.to_lowercase();
+            if token.end < byte && DIVISION_KEYWORDS.contains(&word.as_str()) {
+                division = Some(word);
+            }
+        }
+        if tokens.len() < MAX_TOKENS {
+            tokens.push(token);
+        }
+        tokens.len() < MAX_TOKENS || division.is_none()
+    });
+
+    let mut prefix = String::new();
+    if let Some(token) = tokens.first() {
+        if token.end >= byte && token.is_word() {
+            prefix = token.text.clone();
+            tokens.remove(0);
+        }
+    }
+    let in_define = division.as_deref() == Some("define");
+    let context = get_context(&tokens, point.row, in_define);
+    let operand = match context {
+        CompletionContext::Field(_) => get_operand(tokens.get(2..).unwrap_or_default(), point.row),
+        _ => None,
+    };
+    CompletionTarget {
+        context,
+        prefix,
+        operand,
+    }
+}
+
+/// Passes the tokens before `byte` to `visit`, nearest first, for as long as
+/// it returns true.
+fn walk_tokens_back(
+    root: Node,
+    text: &str,
+    byte: usize,
+    row: usize,
+    mut visit: impl FnMut(Token) -> bool,
+) {
+    let row_of = |at: usize| row - text[at..byte].matches('\n').count();
+    let mut end = byte;
+    let mut leaf = leaf_before(root, byte);
+    loop {
+        // Error recovery may skip text without making leaves of it.
+        let covered = leaf.map_or(0, |node| node.end_byte().min(end));
+        for (start, word) in lex(&text[covered..end]).into_iter().rev() {
+            let start = covered + start;
+            if !visit(Token::new(word, row_of(start), start + word.len())) {
+                return;
+            }
+        }
+        let node = match leaf {
+            Some(node) => node,
+            None => return,
+        };
+        if node.start_byte() < node.end_byte() && node.kind() != "comment" {
+            let word = &text[node.start_byte()..node.end_byte().min(byte)];
+            if !visit(Token::new(word, row_of(node.start_byte()), node.end_byte())) {
+                return;
+            }
+        }
+        end = node.start_byte().min(end);
+        leaf = previous_leaf(node);
+    }
+}
+
+/// Splits text no leaf covers into words and single punctuation characters,
+/// with their offsets.
+fn lex(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        let is_word = c.is_alphanumeric() || c == '_' || c == '$';
+        if let Some(start) = word_start.filter(|_| !is_word) {
+            words.push((start, &text[start..i]));
+            word_start = None;
+        }
+        if is_word {
+            word_start.get_or_insert(i);
+        } else if !c.is_whitespace() {
+            words.push((i, &text[i..i + c.len_utf8()]));
+        }
+    }
+    if let Some(start) = word_start {
+        words.push((start, &text[start..]));
+    }
+    words
+}
+
+fn get_context(tokens: &[Token], row: usize, in_define: bool) -> CompletionContext {
+    let text = |i: usize| tokens.get(i).map(|token| token.text.to_lowercase());
+    let starts_line = tokens.first().is_none_or(|token| token.row < row);
+
+    if text(0).as_deref() == Some("@") {
+        return CompletionContext::SystemVariable;
+    }
+    if in_define {
+        return match text(0).as_deref() {
+            Some("=") => CompletionContext::DeclarationType,
+            _ if starts_line => CompletionContext::DeclarationName,
+            Some("define") => CompletionContext::DeclarationName,
+            _ => CompletionContext::Expression,
+        };
+    }
+    if starts_line {
+        return CompletionContext::Statement;
+    }
+    match (text(0).as_deref(), text(1).as_deref()) {
+        (Some(":"), Some(record)) if is_record_type(record) => {
+            return CompletionContext::Field(normalize_record_type(record));
+        }
+        (Some("="), Some("target")) => return CompletionContext::TargetRecord,
+        (Some("each"), Some("for")) | (Some("any"), _) => return CompletionContext::LoopRecord,
+        (Some("with"), _) => return CompletionContext::WithCondition(with_records(tokens, row)),
+        (Some("call"), _) => return CompletionContext::ProcedureName,
+        (Some("do" | "then" | "else"), _) => return CompletionContext::Statement,
+        _ => {}
+    }
+    match open_call(tokens, row) {
+        Some((function, _)) => CompletionContext::FunctionArgument(function),
+        None => CompletionContext::Expression,
+    }
+}
+
+/// The operand before the assignment or comparison operator that starts
+/// `tokens`, on the cursor's line.
+fn get_operand(tokens: &[Token], row: usize) -> Option<Operand> {
+    let operators = tokens
+        .iter()
+        .take_while(|token| matches!(token.text.as_str(), "=" | "<" | ">" | "<>" | "<=" | ">="))
+        .count();
+    if operators == 0 {
+        return None;
+    }
+    let tokens: Vec<&Token> = tokens[operators..]
+        .iter()
+        .take_while(|token| token.row == row)
+        .collect();
+    let word = tokens.first().filter(|token| token.is_word())?;
+    match (tokens.get(1), tokens.get(2)) {
+        (Some(colon), Some(record)) if colon.text == ":" && is_record_type(&record.text) => {
+            Some(Operand::Field(
+                normalize_record_type(&record.text),
+                word.text.to_uppercase(),
+            ))
+        }
+        _ => Some(Operand::Variable(word.text.to_uppercase())),
+    }
+}
+
+/// The records named between `FOR` or `ANY` and the `WITH` that is the
+/// first token.
+fn with_records(tokens: &[Token], row: usize) -> Vec<String> {
+    let mut records: Vec<String> = tokens
+        .iter()
+        .skip(1)
+        .take_while(|token| token.row == row && is_record_type(&token.text))
+        .map(|token| normalize_record_type(&token.text))
+        .collect();
+    records.reverse();
+    records
+}
+
+/// The builtin whose argument list the cursor is in, and which argument the
+/// cursor is on, counting from 0.
+pub fn call_at(tree: &Tree, text: &str, point: Point) -> Option<(String, usize)> {
+    let byte = point_to_byte(text, point);
+    let mut tokens: Vec<Token> = Vec::new();
+    walk_tokens_back(tree.root_node(), text, byte, point.row, |token| {
+        tokens.push(token);
+        tokens.len() < MAX_TOKENS
+    });
+    open_call(&tokens, point.row)
+}
+
+/// The builtin whose argument list the cursor is in, with the number of
+/// arguments before the cursor's. Only the tokens on the cursor's line are
+/// looked at, as a statement does not span lines.
+fn open_call(tokens: &[Token], row: usize) -> Option<(String, usize)> {
+    let mut depth = 0;
+    let mut argument = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.row < row {
+            return None;
+        }
+        match token.text.as_str() {
+            ")" => depth += 1,
+            "(" if depth > 0 => depth -= 1,
+            "," if depth == 0 => argument += 1,
+            "(" => {
+                let function = tokens.get(i + 1)?.text.to_uppercase();
+                return BUILTIN_FUNCTIONS
+                    .contains_key(function.as_str())
+                    .then_some((function, argument));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A token before the cursor, cut off at the cursor. `end` is where the
+/// token really ends, which is past the cursor inside a word.
+#[derive(Debug)]
+struct Token {
+    text: String,
+    row: usize,
+    end: usize,
+}
+
+impl Token {
+    fn new(text: &str, row: usize, end: usize) -> Self {
+        Self {
+            text: text.to_string(),
+            row,
+            end,
+        }
+    }
+
+    fn is_word(&self) -> bool {
+        !self.text.is_empty()
+            && self
+                .text
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    }
+}
+
+/// The last leaf that starts before `byte`.
+fn leaf_before(root: Node, byte: usize) -> Option<Node> {
+    let mut node = root;
+    loop {
+        let mut cursor = node.walk();
+        let child = node
+            .children(&mut cursor)
+            .filter(|child| child.start_byte() < byte)
+            .last();
+        match child {
+            Some(child) => node = child,
+            None if node.child_count() == 0 && node.start_byte() < byte => return Some(node),
+            None => return None,
+        }
+    }
+}
+
+fn previous_leaf(node: Node) -> Option<Node> {
+    let mut node = node;
+    loop {
+        if let Some(mut sibling) = node.prev_sibling() {
+            while sibling.child_count() > 0 {
+                sibling = sibling.child(sibling.child_count() - 1)?;
+            }
+            return Some(sibling);
+        }
+        node = node.parent()?;
+    }
+}
+
+#[cfg(test)]
+fn target_at_end(source: &str) -> CompletionTarget {
+    let cursor = source.find('|').unwrap();
+    let text = source.replace('|', "");
+    let row = text[..cursor].matches('\n').count();
+    let column = cursor - text[..cursor].rfind('\n').map_or(0, |i| i + 1);
+    let tree = crate::parser::parse(&text).unwrap();
+    completion_target(&tree, &text, Point::new(row, column))
+}
+
+#[test]
+fn test_completion_contexts() {
+    use CompletionContext::*;
+
+    let context = |source| target_at_end(source).context;
+    assert_eq!(context("TARGET=|"), TargetRecord);
+    assert_eq!(
+        context("PRINT TITLE=\"X\"\n IF ACCOUNT:|\nEND"),
+        Field("ACCOUNT".to_string())
+    );
+    assert_eq!(
+        context("PRINT TITLE=\"X\"\n  IF X=1 THEN Y=SHARE TRANSFER:|\nEND"),
+        Field("SHARE TRANSFER".to_string())
+    );
+    assert_eq!(context("PRINT TITLE=\"X\"\n FOR EACH |\nEND"), LoopRecord);
+    assert_eq!(context("PRINT TITLE=\"X\"\n IF ANY |\nEND"), LoopRecord);
+    assert_eq!(
+        context("PRINT TITLE=\"X\"\n FOR EACH SHARE WITH |\nEND"),
+        WithCondition(vec!["SHARE".to_string()])
+    );
+    assert_eq!(context("PRINT TITLE=\"X\"\n X=@|\nEND"), SystemVariable);
+    let target = target_at_end("PRINT TITLE=\"X\"\n X=@ENV|\nEND");
+    assert_eq!(target.context, SystemVariable);
+    assert_eq!(target.prefix, "ENV");
+    assert_eq!(
+        context("PRINT TITLE=\"X\"\n X=ABS(Y+|\nEND"),
+        FunctionArgument("ABS".to_string())
+    );
+    assert_eq!(context("PRINT TITLE=\"X\"\n X=ABS(Y)+|\nEND"), Expression);
+    assert_eq!(context("DEFINE\n X=|\nEND"), DeclarationType);
+    assert_eq!(context("DEFINE\n X=1\n |\nEND"), DeclarationName);
+    assert_eq!(
+        context("DEFINE\n X=\nEND\nPRINT TITLE=\"X\"\n |\nEND"),
+        Statement
+    );
+    assert_eq!(context("PRINT TITLE=\"X\"\n |\nEND"), Statement);
+    assert_eq!(context("PRINT TITLE=\"X\"\n CALL PRI|\nEND"), ProcedureName);
+    assert_eq!(
+        context("PRINT TITLE=\"X\"\n IF X THEN DO\n  |\n END\nEND"),
+        Statement
+    );
+
+    let target = target_at_end("PRINT TITLE=\"X\"\n IF ACCOUNT:OPEN|\nEND");
+    assert_eq!(target.context, Field("ACCOUNT".to_string()));
+    assert_eq!(target.prefix, "OPEN");
+    let target = target_at_end("TARGET=ACC|");
+    assert_eq!(target.context, TargetRecord);
+    assert_eq!(target.prefix, "ACC");
+    let target = target_at_end("DEFINE\n X=NUM|\nEND");
+    assert_eq!(target.context, DeclarationType);
+    assert_eq!(target.prefix, "NUM");
+}
+
+#[test]
+fn test_completion_operands() {
+    let operand = |source| target_at_end(source).operand;
+    assert_eq!(
+        operand("PRINT TITLE=\"X\"\n D=ACCOUNT:|\nEND"),
+        Some(Operand::Variable("D".to_string()))
+    );
+    assert_eq!(
+        operand("PRINT TITLE=\"X\"\n IF ACCOUNT:OPENDATE>ACCOUNT:CL|\nEND"),
+        Some(Operand::Field(
+            "ACCOUNT".to_string(),
+            "OPENDATE".to_string()
+        ))
+    );
+    assert_eq!(operand("PRINT TITLE=\"X\"\n PRINT ACCOUNT:|\nEND"), None);
+    assert_eq!(operand("PRINT TITLE=\"X\"\n D=ABS(X)\n Y=@|\nEND"), None);
+}
+
+#[test]
+fn test_call_at() {
+    let call = |source: &str| {
+        let cursor = source.find('|').unwrap();
+        let text = source.replace('|', "");
+        let row = text[..cursor].matches('\n').count();
+        let column = cursor - text[..cursor].rfind('\n').map_or(0, |i| i + 1);
+        let tree = crate::parser::parse(&text).unwrap();
+        call_at(&tree, &text, Point::new(row, column))
+    };
+    assert_eq!(
+        call("PRINT TITLE=\"X\"\n X=SEGMENT(Y,|\nEND"),
+        Some(("SEGMENT".to_string(), 1))
+    );
+    assert_eq!(
+        call("PRINT TITLE=\"X\"\n X=SEGMENT(ABS(Y),LENGTH(Z),|)\nEND"),
+        Some(("SEGMENT".to_string(), 2))
+    );
+    assert_eq!(
+        call("PRINT TITLE=\"X\"\n X=SEGMENT(ABS(Y|\nEND"),
+        Some(("ABS".to_string(), 0))
+    );
+    assert_eq!(call("PRINT TITLE=\"X\"\n X=ABS(Y)+|\nEND"), None);
+}
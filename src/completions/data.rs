@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tower_lsp::lsp_types::CompletionItem;
+
+/// What a completion item stands for, sent along with the item so
+/// `completionItem/resolve` can look up its documentation. Lists only carry
+/// short descriptions, which keeps them small.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CompletionData {
+    Field { record: String, mnemonic: String },
+    Function { name: String },
+    SystemVariable { name: String },
+}
+
+impl CompletionData {
+    pub fn attach(self, mut item: CompletionItem) -> CompletionItem {
+        item.data = serde_json::to_value(self).ok();
+        item
+    }
+
+    pub fn from_item(item: &CompletionItem) -> Option<Self> {
+        let data: Value = item.data.clone()?;
+        serde_json::from_value(data).ok()
+    }
+}
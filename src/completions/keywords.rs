@@ -0,0 +1,43 @@
+use lazy_static::lazy_static;
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, Documentation, InsertTextFormat};
+
+lazy_static! {
+    /// Keywords that start a statement, with snippets for their blocks.
+    pub static ref STATEMENT_KEYWORD_COMPLETIONS: Vec<CompletionItem> = vec![
+        keyword(
+            "IF",
+            "IF ${1:Condition} THEN\n DO\n  $0\n END",
+            "Runs the block when the condition is TRUE.",
+        ),
+        keyword("ELSE", "ELSE\n DO\n  $0\n END", "Runs the block when the IF condition is FALSE."),
+        keyword(
+            "WHILE",
+            "WHILE ${1:Condition}\n DO\n  $0\n END",
+            "Runs the block for as long as the condition is TRUE.",
+        ),
+        keyword(
+            "FOR",
+            "FOR ${1:I}=${2:1} TO ${3:10}\n DO\n  $0\n END",
+            "Runs the block once for every value of the loop variable.",
+        ),
+        keyword(
+            "FOR EACH",
+            "FOR EACH ${1:Record}\n DO\n  $0\n END",
+            "Runs the block once for every record of the type below the current record.",
+        ),
+        keyword("CALL", "CALL ${1:Procedure}", "Runs a procedure."),
+        keyword("DO", "DO\n $0\nEND", "Starts a block of statements."),
+        keyword("END", "END", "Ends a block or division."),
+    ];
+}
+
+fn keyword(label: &str, snippet: &str, documentation: &str) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        insert_text: Some(snippet.to_string()),
+        kind: Some(CompletionItemKind::KEYWORD),
+        insert_text_format: Some(InsertTextFormat::SNIPPET),
+        documentation: Some(Documentation::String(documentation.to_string())),
+        ..CompletionItem::default()
+    }
+}
@@ -1 +1,4 @@
-pub mod poweron_functions;
+pub mod context;
+pub mod data;
+pub mod keywords;
+pub mod ranking;
@@ -0,0 +1,41 @@
+/// How well `pattern` matches `candidate`, ignoring case, or None when it
+/// does not. Every character of the pattern must appear in the candidate in
+/// order. Matches at the start and runs of consecutive characters score
+/// higher, so `open` ranks `OPENDATE` above `OVERPAYMENTEND`.
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<u32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for c in pattern.to_lowercase().chars() {
+        let found = next + candidate[next..].iter().position(|&other| other == c)?;
+        score += 1;
+        if found == 0 {
+            score += 8;
+        }
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 4;
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// Whether a word of `text` starts with `pattern`, ignoring case.
+pub fn matches_word(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.to_lowercase().starts_with(&pattern))
+}
+
+#[test]
+fn test_fuzzy_score() {
+    let opendate = fuzzy_score("open", "OPENDATE").unwrap();
+    let overpayment = fuzzy_score("open", "OVERPAYMENTEND").unwrap();
+    assert!(opendate > overpayment);
+    assert!(fuzzy_score("opdt", "OPENDATE").is_some());
+    assert!(fuzzy_score("dateo", "OPENDATE").is_none());
+    assert!(matches_word("open", "The date the account was opened."));
+    assert!(!matches_word("pen", "The date the account was opened."));
+}
@@ -6,7 +6,7 @@ use super::types::{DataType, DatabaseField};
 
 lazy_static! {
     pub static ref ACCOUNT_RECORD_FIELDS: HashMap<&'static str, DatabaseField> =
-    load_account_record_fields();
+        load_account_record_fields();
 }
 
 fn load_account_record_fields() -> HashMap<&'static str, DatabaseField> {
@@ -131,7 +131,7 @@ Enter a credit union-defined code (0–9999) to identify the group to which the
         },
     );
 
-    account_fields.insert( 
+    account_fields.insert(
         "restrict",
         DatabaseField {
             data_type: DataType::Code,
@@ -194,7 +194,7 @@ If an account has a **Restricted Access** code of **1-6**, you can suppress the
     );
 
     account_fields.insert(
-        "opendate", 
+        "opendate",
         DatabaseField {
             data_type: DataType::Date,
             default_control: false,
@@ -218,7 +218,8 @@ Default Value   Date Null
 The system updates this field with the system date when the Account record is created.
 
 Under normal circumstances, you should not revise this field."#,
-        });
+        },
+    );
 
     account_fields.insert(
         "lastfmdate",
@@ -336,11 +337,11 @@ You cannot perform file maintenance on this field.
         "prgdrecactivitydt",
         DatabaseField {
             data_type: DataType::Date,
-            default_control: false, 
+            default_control: false,
             default_value: "Date Null", 
             field_number: 56,
             help_file: "00056", 
-            length: None, 
+            length: None,
             mnemonic: "prgdrecactivitydt", 
             description: "This field stores the most recent activity date from shares or loans that are purged.",
             details: r#"
@@ -393,12 +394,12 @@ States with this requirement also specify a period after which the credit union
     account_fields.insert(
         "proxydate", 
         DatabaseField {
-            data_type: DataType::Date, 
-            default_control: true, 
+            data_type: DataType::Date,
+            default_control: true,
             default_value: "Date Null", 
-            field_number: 15, 
+            field_number: 15,
             help_file: "00015", 
-            length: None, 
+            length: None,
             mnemonic: "proxydate", 
             description: "If your credit union allows members to assign their vote to a proxy, this field stores the expiration date or the effective date of the proxy.",
             details: r#"
@@ -416,15 +417,15 @@ Default Value   Date Null
         }
     );
 
-    account_fields.insert( 
+    account_fields.insert(
         "closedate", 
         DatabaseField {
-            data_type: DataType::Date, 
-            default_control: true, 
+            data_type: DataType::Date,
+            default_control: true,
             default_value: "Date Null", 
-            field_number: 6, 
+            field_number: 6,
             help_file: "00006", 
-            length: None, 
+            length: None,
             mnemonic: "closedate", 
             description: "This field stores the date the account was closed.", 
             details: r#"
@@ -483,7 +484,7 @@ You cannot perform file maintenance on this field."#,
     account_fields.insert(
         "reference",
         DatabaseField {
-            data_type: DataType::Character, 
+            data_type: DataType::Character,
             default_control: true,
             default_value: "",
             field_number: 9,
@@ -547,11 +548,11 @@ For any Name record associated with an account with this option, the value in th
         "commercialcode", 
         DatabaseField {
             data_type: DataType::Code,
-            default_control: true, 
+            default_control: true,
             default_value: "0", 
-            field_number: 21, 
+            field_number: 21,
             help_file: "00021", 
-            length: Some(2), 
+            length: Some(2),
             mnemonic: "commercialcode", 
             description: "This field stores a code that indicates whether an account is a consumer account, small business account, or commercial account.", 
             details: r#"
@@ -613,9 +614,9 @@ If the ***KR Hold Base Option*** parameter in the Regulation CC Parameters is se
             data_type: DataType::Money,
             default_control: false,
             default_value: "0.00", 
-            field_number: 37, 
+            field_number: 37,
             help_file: "00037", 
-            length: None, 
+            length: None,
             mnemonic: "krtotalamount", 
             description: "This field stores a running total of any non-held portions of check deposits for the date stored in the Check Dep Total Date field.", 
             details: r#"
@@ -646,9 +647,9 @@ When you void a Checks Received (KR) transaction or a Checks Cashing (KC) transa
             data_type: DataType::Date,
             default_control: false,
             default_value: "Date Null",
-            field_number: 38, 
+            field_number: 38,
             help_file: "00038", 
-            length: None, 
+            length: None,
             mnemonic: "krtotaldate", 
             description: "This field stores the date of the last check deposit that affected the Account record Check Dep Total Amount field or the Non-Reg CC Check Dep Total Amt field.",
             details: r#"
@@ -679,7 +680,7 @@ When the system processes a transaction that affects either of these two Account
             default_value: "0.00",
             field_number: 64,
             help_file: "00064", 
-            length: None, 
+            length: None,
             mnemonic: "nonregcccheckholdbaseamt",
             description: "This field stores the date of the last check deposit that affected the Account record Check Dep Total Amount field or the Non-Reg CC Check Dep Total Amt field.",
             details: r#"
@@ -733,7 +734,7 @@ Default Value   0.00"#,
             default_value: "0",
             field_number: 53,
             help_file: "00053", 
-            length: Some(1), 
+            length: Some(1),
             mnemonic: "enablefloat", 
             description: "This field stores a code that indicates whether to activate credit union floats per account.",
             details: r#"
@@ -763,12 +764,12 @@ Enable CU float holds as defined in Analysis CU Float Parameters.
     account_fields.insert(
         "currentrelationshipcode", 
         DatabaseField {
-            data_type: DataType::Code, 
-            default_control: false, 
+            data_type: DataType::Code,
+            default_control: false,
             default_value: "0", 
-            field_number: 808, 
+            field_number: 808,
             help_file: "000808", 
-            length: Some(99), 
+            length: Some(99),
             mnemonic: "currentrelationshipcode", 
             description: "This calculated field contains a code that specifies the member's current relationship to your credit union.",
             details: r#"
@@ -800,7 +801,7 @@ You cannot perform direct file maintenance of this field. To change the value of
             data_type: DataType::Code,
             default_control: true,
             default_value: "0",
-            field_number: 032,
+            field_number: 32,
             help_file: "00032",
             length: Some(99),
             mnemonic: "relationshipcode",
@@ -824,11 +825,11 @@ The system enters a code in this field (0–99) that appears in various inquirie
         "relationshipoverride", 
         DatabaseField {
             data_type: DataType::Code,
-            default_control: true, 
+            default_control: true,
             default_value: "0", 
-            field_number: 033, 
+            field_number: 33,
             help_file: "00033", 
-            length: Some(99), 
+            length: Some(99),
             mnemonic: "relationshipoverride", 
             description: "This field stores a code that specifies a relationship code that overrides the base relationship code until the relationship override expires.",
             details: r#"
@@ -849,10 +850,10 @@ Enter a code in this field (0–99) that appears in various inquiries and calcul
     account_fields.insert(
         "relationshipoverrideeffdate", 
         DatabaseField {
-            data_type: DataType::Date, 
-            default_control: true, 
+            data_type: DataType::Date,
+            default_control: true,
             default_value: "Date Null",
-            field_number: 034, 
+            field_number: 34,
             help_file: "00034", 
             length: None,
             mnemonic: "relationshipoverrideeffdate",
@@ -876,7 +877,7 @@ Default Value   Date Null "#,
             data_type: DataType::Date,
             default_control: true,
             default_value: "Date Null",
-            field_number: 035,
+            field_number: 35,
             help_file: "00035",
             length: None,
             mnemonic: "relationshipoverrideexpdate",
@@ -900,7 +901,7 @@ Default Value   Date Null "#,
             data_type: DataType::Code,
             default_control: true,
             default_value: "0",
-            field_number: 016,
+            field_number: 16,
             help_file: "00016",
             length: Some(1),
             mnemonic: "headofhousehold",
@@ -937,7 +938,7 @@ You can access the ***Head of Household*** field with PowerOn to target marketin
             data_type: DataType::Character,
             default_control: false,
             default_value: "",
-            field_number: 025,
+            field_number: 25,
             help_file: "00025",
             length: Some(10),
             mnemonic: "householdaccount",
@@ -967,11 +968,11 @@ Whenever you perform file maintenance on this field, the system automatically cr
         "householdstatement", 
         DatabaseField {
             data_type: DataType::Code,
-            default_control: false, 
+            default_control: false,
             default_value: "0",
-            field_number: 026, 
+            field_number: 26,
             help_file: "00026", 
-            length: Some(1), 
+            length: Some(1),
             mnemonic: "householdstatement", 
             description: "This field stores a code that indicates if you want to send multiple statements to one household in a single envelope.",
             details: r#"
@@ -1002,7 +1003,7 @@ Group this statement with the head of household statement for mailing. "#,
             data_type: DataType::Code,
             default_control: true,
             default_value: "0",
-            field_number: 031,
+            field_number: 31,
             help_file: "00031",
             length: Some(99),
             mnemonic: "statementmailcode",
@@ -1039,7 +1040,7 @@ If you enter a mail code that is defined as No Mail, statements for the entire a
             data_type: DataType::Code,
             default_control: true,
             default_value: "0",
-            field_number: 039,
+            field_number: 39,
             help_file: "00039",
             length: Some(1),
             mnemonic: "estmtnotify",
@@ -1070,7 +1071,7 @@ The user should receive email notification that an e-statement is available. "#,
             data_type: DataType::Code,
             default_control: true,
             default_value: "0",
-            field_number: 040,
+            field_number: 40,
             help_file: "00040",
             length: Some(2),
             mnemonic: "estmtenable",
@@ -1104,7 +1105,7 @@ The member wants to receive both an e-statement and a printed statement. "#,
             data_type: DataType::Character,
             default_control: true,
             default_value: "",
-            field_number: 024,
+            field_number: 24,
             help_file: "00024",
             length: Some(2),
             mnemonic: "statereporting",
@@ -1146,7 +1147,7 @@ Default Value   (Blank)
             data_type: DataType::Code,
             default_control: false,
             default_value: "Current user number",
-            field_number: 060,
+            field_number: 60,
             help_file: "00060",
             length: Some(9999),
             mnemonic: "createdbyuser",
@@ -1176,16 +1177,17 @@ Under normal circumstances, you should not revise this field.
     );
 
     account_fields.insert(
-        "createdatbranch", 
+        "createdatbranch",
         DatabaseField {
             data_type: DataType::Code,
             default_control: false,
             default_value: "Current user branch",
-            field_number: 061,
+            field_number: 61,
             help_file: "00061",
             length: Some(9999),
             mnemonic: "createdatbranch",
-            description: "This field stores the branch ID of the branch where the record is created.",
+            description:
+                "This field stores the branch ID of the branch where the record is created.",
             details: r#"
 # Created At Branch 
 
@@ -1205,7 +1207,7 @@ The system is unable to determine the information because it is not available.
 The branch ID where the record was created.
 
 Under normal circumstances, you should not revise this field. "#,
-        }
+        },
     );
 
     account_fields.insert(
@@ -1214,7 +1216,7 @@ Under normal circumstances, you should not revise this field. "#,
             data_type: DataType::Money,
             default_control: false,
             default_value: "0.00",
-            field_number: 041,
+            field_number: 41,
             help_file: "00041",
             length: None,
             mnemonic: "crtotalamount",
@@ -1246,7 +1248,7 @@ The system sets the totals for this field to $0.00 if the ***New Banking Day***
             data_type: DataType::Money,
             default_control: false,
             default_value: "0.00",
-            field_number: 042,
+            field_number: 42,
             help_file: "00042",
             length: None,
             mnemonic: "cdtotalamount",
@@ -0,0 +1,1461 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+use super::types::{Availability, BuiltinFunction, DataType, Parameter, ReturnType, Syntax};
+
+lazy_static! {
+    /// Builtin functions keyed by their name.
+    pub static ref BUILTIN_FUNCTIONS: HashMap<&'static str, BuiltinFunction> =
+        load_builtin_functions();
+}
+
+const CHARACTER: DataType = DataType::Character;
+const CODE: DataType = DataType::Code;
+const DATE: DataType = DataType::Date;
+const FLOAT: DataType = DataType::Float;
+const MONEY: DataType = DataType::Money;
+const NUMBER: DataType = DataType::Number;
+const RATE: DataType = DataType::Rate;
+
+const SERVICE_RECORDS: &[&str] = &["SHARE", "LOAN", "CARD", "EXTERNALLOAN"];
+const WARNING_RECORDS: &[&str] = &["ACCOUNT", "SHARE", "LOAN", "CARD", "EXTERNALLOAN"];
+
+fn load_builtin_functions() -> HashMap<&'static str, BuiltinFunction> {
+    builtin_functions()
+        .into_iter()
+        .map(|function| (function.name, function))
+        .collect()
+}
+
+fn builtin_functions() -> Vec<BuiltinFunction> {
+    let mut functions = Vec::new();
+    functions.extend(math_functions());
+    functions.extend(string_functions());
+    functions.extend(date_functions());
+    functions.extend(prompt_functions());
+    functions.extend(dialog_functions());
+    functions.extend(window_functions());
+    functions.extend(output_functions());
+    functions.extend(printer_functions());
+    functions.extend(file_functions());
+    functions.extend(transfer_functions());
+    functions.extend(file_maintenance_functions());
+    functions.extend(field_functions());
+    functions.extend(other_functions());
+    functions
+}
+
+fn math_functions() -> Vec<BuiltinFunction> {
+    vec![
+        call(
+            "ABS",
+            vec![any("Expression")],
+            ReturnType::Argument,
+            "Returns the absolute value of an expression.",
+        ),
+        call(
+            "EXP",
+            vec![any("Expression")],
+            ReturnType::Value(FLOAT),
+            "Returns the mathematical constant e raised to the power of the expression.",
+        ),
+        call(
+            "FLOAT",
+            vec![any("Expression")],
+            ReturnType::Value(FLOAT),
+            "Converts a number, money, rate, date or code value to its floating point value. Use it on a whole expression: on part of a compound expression the result is unpredictable.",
+        ),
+        call(
+            "FLOOR",
+            vec![any("Expression")],
+            ReturnType::Argument,
+            "Returns the largest whole number less than or equal to the expression.",
+        ),
+        call(
+            "INT",
+            vec![any("Expression")],
+            ReturnType::Argument,
+            "Returns the whole number part of a number, money amount or floating point expression.",
+        ),
+        call(
+            "LOG",
+            vec![any("Expression")],
+            ReturnType::Value(FLOAT),
+            "Returns the natural logarithm of a number, code or floating point value.",
+        ),
+        call(
+            "MOD",
+            vec![arg("Dividend", NUMBER), arg("Divisor", NUMBER)],
+            ReturnType::Value(NUMBER),
+            "Returns the remainder of dividing the dividend by the divisor.",
+        ),
+        call(
+            "PWR",
+            vec![any("Base"), any("Exponent")],
+            ReturnType::Value(FLOAT),
+            "Returns the base raised to the power of the exponent.",
+        ),
+        call(
+            "MONEY",
+            vec![any("Expression")],
+            ReturnType::Value(MONEY),
+            "Converts a number, code, float or rate value to a money amount. Use it on a whole expression: on part of a compound expression the result is unpredictable.",
+        ),
+        call(
+            "NUMBER",
+            vec![any("Expression")],
+            ReturnType::Value(NUMBER),
+            "Converts a money, code, float, rate or date value to a number, dropping any fraction.",
+        ),
+        call(
+            "RATE",
+            vec![any("Expression")],
+            ReturnType::Value(RATE),
+            "Converts a number, money or float value to a rate.",
+        ),
+    ]
+}
+
+fn string_functions() -> Vec<BuiltinFunction> {
+    vec![
+        call(
+            "AFTERLAST",
+            vec![arg("Text", CHARACTER), arg("Search", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the characters of the text after the last occurrence of the search text, or the whole text when it does not occur.",
+        ),
+        call(
+            "BEFOREFIRST",
+            vec![arg("Text", CHARACTER), arg("Search", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the characters of the text before the first occurrence of the search text, or the whole text when it does not occur.",
+        ),
+        call(
+            "CAPITALIZE",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Translates the first character of each word to uppercase and all other letters to lowercase.",
+        ),
+        call(
+            "CHARACTERSEARCH",
+            vec![arg("Text", CHARACTER), arg("Search", CHARACTER)],
+            ReturnType::Value(NUMBER),
+            "Returns the position of the first occurrence of the search text within the text, or 0 when it does not occur.",
+        ),
+        call(
+            "CHRVALUE",
+            vec![arg("Character", CHARACTER)],
+            ReturnType::Value(NUMBER),
+            "Returns the decimal ASCII value of a single character.",
+        ),
+        call(
+            "CTRLCHR",
+            vec![arg("Code", NUMBER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the non-printing control character with the ASCII code, to send to a printer for special features such as fonts, bold type and boxes.",
+        ),
+        call(
+            "FORMAT",
+            vec![arg("Format", CHARACTER), any("Expression")],
+            ReturnType::Value(CHARACTER),
+            "Formats an expression according to the format string, such as \"999-99-9999\" for a social security number.",
+        ),
+        call(
+            "LENGTH",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(NUMBER),
+            "Returns the number of characters in a character string.",
+        ),
+        call(
+            "LOWERCASE",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Converts the letters of a character expression to lowercase.",
+        ),
+        call(
+            "UPPERCASE",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Converts the letters of a character expression to uppercase.",
+        ),
+        call(
+            "REPEATCHR",
+            vec![arg("Text", CHARACTER), arg("Count", NUMBER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the text repeated the given number of times.",
+        ),
+        call(
+            "SEGMENT",
+            vec![
+                arg("Text", CHARACTER),
+                arg("Start", NUMBER),
+                arg("End", NUMBER),
+            ],
+            ReturnType::Value(CHARACTER),
+            "Returns the characters of the text from the start position to the end position, counting from 1.",
+        ),
+        call(
+            "VALUE",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(NUMBER),
+            "Converts the digits in a character string to a number.",
+        ),
+        call(
+            "MD5HASH",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the MD5 hash of a character string. It is used with PASSWORDHASH to encrypt audio access codes and home banking passwords.",
+        ),
+        call(
+            "PASSWORDHASH",
+            vec![arg("Password", CHARACTER)],
+            ReturnType::Value(CHARACTER),
+            "Encrypts access codes and home banking passwords.",
+        ),
+    ]
+}
+
+fn date_functions() -> Vec<BuiltinFunction> {
+    vec![
+        call(
+            "DATE",
+            vec![arg("Month", NUMBER), arg("Day", NUMBER), arg("Year", NUMBER)],
+            ReturnType::Value(DATE),
+            "Converts three numeric expressions into a date.",
+        ),
+        call(
+            "DATEOFFSET",
+            vec![arg("Date", DATE), arg("Months", NUMBER), arg("Days", NUMBER)],
+            ReturnType::Value(DATE),
+            "Returns the date that is the given number of months and days after the date. Negative counts go back.",
+        ),
+        call(
+            "DATEVALUE",
+            vec![arg("Text", CHARACTER)],
+            ReturnType::Value(DATE),
+            "Converts a date stored as character data to a date.",
+        ),
+        call(
+            "DAY",
+            vec![arg("Date", DATE)],
+            ReturnType::Value(NUMBER),
+            "Returns the day of the month of a date.",
+        ),
+        call(
+            "DAYOFWEEK",
+            vec![arg("Date", DATE)],
+            ReturnType::Value(NUMBER),
+            "Returns the day of the week of a date, from 0 for Sunday to 6 for Saturday.",
+        ),
+        call(
+            "MONTH",
+            vec![arg("Date", DATE)],
+            ReturnType::Value(NUMBER),
+            "Returns the month of a date, from 1 for January to 12 for December.",
+        ),
+        call(
+            "YEAR",
+            vec![arg("Date", DATE)],
+            ReturnType::Value(NUMBER),
+            "Returns the two-digit year of a date.",
+        ),
+        call(
+            "FULLYEAR",
+            vec![arg("Date", DATE)],
+            ReturnType::Value(NUMBER),
+            "Returns the four-digit year of a date, from 1900 to 2078.",
+        ),
+        call(
+            "HOUR",
+            vec![arg("Time", NUMBER)],
+            ReturnType::Value(NUMBER),
+            "Returns the hour, from 0 to 23, of a time in HHMM format.",
+        ),
+        call(
+            "MINUTE",
+            vec![arg("Time", NUMBER)],
+            ReturnType::Value(NUMBER),
+            "Returns the minute, from 0 to 59, of a time in HHMM format.",
+        ),
+        BuiltinFunction {
+            syntax: Syntax::Keyword,
+            ..call(
+                "SYSTEMDATE",
+                vec![],
+                ReturnType::Value(DATE),
+                "The system date of the credit union, which is the posting date rather than the calendar date.",
+            )
+        },
+    ]
+}
+
+fn prompt_functions() -> Vec<BuiltinFunction> {
+    let mut functions = vec![
+        call(
+            "ENTERCHARACTER",
+            vec![
+                arg("Prompt", CHARACTER),
+                arg("MaxLength", NUMBER),
+                optional(arg("Default", CHARACTER)),
+            ],
+            ReturnType::Value(CHARACTER),
+            "Prompts the user for characters, up to the maximum length, and returns them.",
+        ),
+        call(
+            "ENTERCODE",
+            vec![
+                arg("Prompt", CHARACTER),
+                arg("MaxValue", NUMBER),
+                optional(arg("Default", CODE)),
+            ],
+            ReturnType::Value(CODE),
+            "Prompts the user for a code, up to the maximum value, and returns it.",
+        ),
+        call(
+            "ENTERDATE",
+            vec![arg("Prompt", CHARACTER), optional(arg("Default", DATE))],
+            ReturnType::Value(DATE),
+            "Prompts the user for a date and returns it.",
+        ),
+        call(
+            "ENTERMONEY",
+            vec![arg("Prompt", CHARACTER), optional(arg("Default", MONEY))],
+            ReturnType::Value(MONEY),
+            "Prompts the user for a money amount and returns it.",
+        ),
+        call(
+            "ENTERNUMBER",
+            vec![
+                arg("Prompt", CHARACTER),
+                arg("MaxValue", NUMBER),
+                optional(arg("Default", NUMBER)),
+            ],
+            ReturnType::Value(NUMBER),
+            "Prompts the user for a number, up to the maximum value, and returns it.",
+        ),
+        call(
+            "ENTERRATE",
+            vec![arg("Prompt", CHARACTER), optional(arg("Default", RATE))],
+            ReturnType::Value(RATE),
+            "Prompts the user for a rate and returns it.",
+        ),
+        call(
+            "ENTERYESNO",
+            vec![
+                arg("Prompt", CHARACTER),
+                optional(arg("Default", CHARACTER)),
+            ],
+            ReturnType::Value(CHARACTER),
+            "Prompts the user for a yes or no answer and returns Y or N.",
+        ),
+    ];
+    let read_functions = [
+        ("CHARACTERREAD", CHARACTER, "Use ENTERCHARACTER."),
+        ("CODEREAD", CODE, "Use ENTERCODE."),
+        ("DATEREAD", DATE, "Use ENTERDATE."),
+        ("MONEYREAD", MONEY, "Use ENTERMONEY."),
+        ("NUMBERREAD", NUMBER, "Use ENTERNUMBER."),
+        ("RATEREAD", RATE, "Use ENTERRATE."),
+        ("YESNOREAD", CHARACTER, "Use ENTERYESNO."),
+    ];
+    for (name, data_type, replacement) in read_functions {
+        functions.push(BuiltinFunction {
+            deprecated: Some(replacement),
+            ..call(
+                name,
+                vec![arg("Prompt", CHARACTER)],
+                ReturnType::Value(data_type),
+                "Displays a prompt on the user's console and returns the response.",
+            )
+        });
+    }
+    functions.push(BuiltinFunction {
+        deprecated: Some("Use ENTERYESNO."),
+        ..call(
+            "YESNOPROMPT",
+            vec![arg("Prompt", CHARACTER)],
+            ReturnType::Boolean,
+            "Displays a prompt on the user's console and is TRUE when the user answers yes.",
+        )
+    });
+    functions
+        .into_iter()
+        .map(|function| BuiltinFunction {
+            availability: Availability::INTERACTIVE,
+            ..function
+        })
+        .collect()
+}
+
+fn dialog_functions() -> Vec<BuiltinFunction> {
+    let functions = vec![
+        statement(
+            "DIALOGSTART",
+            vec![
+                arg("Title", CHARACTER),
+                arg("WidthRatio", RATE),
+                choice("Mode", &["0", "1"]),
+            ],
+            "Starts a dialog of prompts in an interactive window. The prompts that follow it are shown together when DIALOGDISPLAY is reached.",
+        ),
+        keyword("DIALOGDISPLAY", "Displays the dialog started by DIALOGSTART and waits for the user to answer it."),
+        keyword("DIALOGCLOSE", "Closes the dialog."),
+        statement(
+            "DIALOGINTROTEXT",
+            vec![arg("Text", CHARACTER)],
+            "Adds a line of introductory text to the top of the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTCHAR",
+            vec![
+                arg("Prompt", CHARACTER),
+                arg("MaxLength", NUMBER),
+                optional(arg("Default", CHARACTER)),
+            ],
+            "Adds a prompt for characters to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTCODE",
+            vec![
+                arg("Prompt", CHARACTER),
+                arg("MaxValue", NUMBER),
+                optional(arg("Default", CODE)),
+            ],
+            "Adds a prompt for a code to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTDATE",
+            vec![arg("Prompt", CHARACTER), arg("Default", DATE)],
+            "Adds a prompt for a date to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTMONEY",
+            vec![arg("Prompt", CHARACTER), arg("Default", MONEY)],
+            "Adds a prompt for a money amount to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTNUMBER",
+            vec![arg("Prompt", CHARACTER), arg("Default", NUMBER)],
+            "Adds a prompt for a number to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTPASSWORD",
+            vec![
+                arg("Prompt", CHARACTER),
+                arg("MaxLength", NUMBER),
+                optional(arg("Default", CHARACTER)),
+            ],
+            "Adds a prompt for a password to the dialog. What the user types is not shown.",
+        ),
+        statement(
+            "DIALOGPROMPTRATE",
+            vec![arg("Prompt", CHARACTER), arg("Default", RATE)],
+            "Adds a prompt for a rate to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTYESNO",
+            vec![arg("Prompt", CHARACTER), arg("Default", CHARACTER)],
+            "Adds a drop-down list for a yes or no answer to the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTCOMBOSTART",
+            vec![arg("Prompt", CHARACTER), any("Default")],
+            "Starts a drop-down list of options in the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTCOMBOOPTION",
+            vec![any("Value"), arg("Text", CHARACTER)],
+            "Adds an option to the drop-down list.",
+        ),
+        keyword("DIALOGPROMPTCOMBOEND", "Ends the drop-down list of options."),
+        statement(
+            "DIALOGPROMPTLISTSTART",
+            vec![arg("Prompt", CHARACTER), any("Default")],
+            "Starts a list box of options in the dialog.",
+        ),
+        statement(
+            "DIALOGPROMPTLISTOPTION",
+            vec![any("Value"), arg("Text", CHARACTER)],
+            "Adds an option to the list box.",
+        ),
+        keyword("DIALOGPROMPTLISTEND", "Ends the list box of options."),
+        statement(
+            "DIALOGSTARTGROUPBOX",
+            vec![arg("Title", CHARACTER)],
+            "Starts a group box around the prompts that follow in the dialog.",
+        ),
+        keyword("DIALOGENDGROUPBOX", "Ends the group box."),
+        statement(
+            "DIALOGTEXTLISTSTART",
+            vec![arg("Title", CHARACTER)],
+            "Starts a list box of read-only lines in a pop-up window.",
+        ),
+        statement(
+            "DIALOGTEXTLISTOPTION",
+            vec![arg("Text", CHARACTER)],
+            "Adds a line to the read-only list box.",
+        ),
+        keyword("DIALOGTEXTLISTEND", "Ends the read-only list box."),
+    ];
+    functions
+        .into_iter()
+        .map(|function| BuiltinFunction {
+            availability: Availability::WINDOW,
+            ..function
+        })
+        .collect()
+}
+
+fn window_functions() -> Vec<BuiltinFunction> {
+    let mut functions: Vec<BuiltinFunction> = vec![
+        statement(
+            "HTMLVIEWOPEN",
+            vec![optional(choice("Mode", &["0", "1"]))],
+            "Opens an HTML view window.",
+        ),
+        statement(
+            "HTMLVIEWLINE",
+            vec![arg("Html", CHARACTER)],
+            "Adds a line of HTML to the view window.",
+        ),
+        keyword(
+            "HTMLVIEWDISPLAY",
+            "Displays the HTML view window built by HTMLVIEWLINE.",
+        ),
+        statement(
+            "WINMESSAGESTART",
+            vec![arg("Title", CHARACTER)],
+            "Starts a message window.",
+        ),
+        statement(
+            "WINMESSAGEFIELD",
+            vec![arg("Label", CHARACTER), any("Value")],
+            "Adds a labeled value to the message window.",
+        ),
+        statement(
+            "WINDDECONNECT",
+            vec![
+                arg("Application", CHARACTER),
+                arg("Topic", CHARACTER),
+                arg("Channel", NUMBER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Opens a DDE conversation with a Windows application on the user's workstation.",
+        ),
+        statement(
+            "WINDDEEXECUTE",
+            vec![
+                arg("Channel", NUMBER),
+                arg("Command", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Sends a command to the application of a DDE conversation.",
+        ),
+        statement(
+            "WINDDEPOKEDATA",
+            vec![
+                arg("Channel", NUMBER),
+                arg("Item", CHARACTER),
+                arg("Data", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Sends data to an item of the application of a DDE conversation.",
+        ),
+        statement(
+            "WINDOWSSEND",
+            vec![arg("Text", CHARACTER), arg("ErrorText", CHARACTER)],
+            "Sends text to the user's workstation.",
+        ),
+    ]
+    .into_iter()
+    .map(|function| BuiltinFunction {
+        availability: Availability::WINDOW,
+        ..function
+    })
+    .collect();
+    functions.push(BuiltinFunction {
+        availability: Availability::INTERACTIVE,
+        ..statement(
+            "POPUPMESSAGE",
+            vec![
+                choice("Icon", &["0", "1", "2", "3"]),
+                arg("Message", CHARACTER),
+            ],
+            "Displays a message in a pop-up window on the user's console.",
+        )
+    });
+    functions
+}
+
+fn output_functions() -> Vec<BuiltinFunction> {
+    vec![
+        BuiltinFunction {
+            syntax: Syntax::Prefix,
+            ..statement(
+                "PRINT",
+                vec![any("Expression")],
+                "Prints the value of an expression.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Assignment,
+            ..statement(
+                "COL",
+                vec![arg("Column", NUMBER)],
+                "Moves the output to a column, where the next PRINT starts.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Assignment,
+            ..statement(
+                "DATASIZE",
+                vec![arg("Size", NUMBER), any("Expression")],
+                "Writes an expression of the given size to the data file of a DATAFILE specfile.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Assignment,
+            ..statement(
+                "HEADER",
+                vec![arg("Headings", CHARACTER)],
+                "Defines a line of column headings and where they are placed.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Block,
+            ..statement(
+                "HEADERS",
+                vec![],
+                "Prints the statements up to END at the top of every page of the report section.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Block,
+            ..statement(
+                "TRAILERS",
+                vec![],
+                "Prints the statements up to END at the bottom of every page of the report section.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Assignment,
+            ..statement(
+                "WIDTH",
+                vec![arg("Width", NUMBER)],
+                "Sets the width of the report in characters.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Assignment,
+            ..statement(
+                "FORMLENGTH",
+                vec![arg("Lines", NUMBER)],
+                "Sets the number of lines on a page of the report. Without a value, the report is not divided into pages.",
+            )
+        },
+        keyword("NEWLINE", "Ends the current line of output."),
+        keyword(
+            "SUPPRESSNEWLINE",
+            "Keeps the next PRINT on the same line as the last.",
+        ),
+        BuiltinFunction {
+            availability: Availability::INTERACTIVE,
+            ..keyword(
+                "DIM",
+                "Returns the screen to normal display after characters are displayed with BRIGHT.",
+            )
+        },
+        BuiltinFunction {
+            availability: Availability::INTERACTIVE,
+            ..keyword("STOPBLINK", "Stops blinking characters on the user's screen.")
+        },
+        BuiltinFunction {
+            availability: Availability::INTERACTIVE,
+            ..statement(
+                "SCREENXYPOS",
+                vec![arg("Column", NUMBER), arg("Row", NUMBER)],
+                "Moves the cursor to a position on the user's screen.",
+            )
+        },
+        BuiltinFunction {
+            availability: Availability::SPECFILE,
+            ..statement(
+                "OUTPUTOPEN",
+                vec![
+                    arg("DeviceType", NUMBER),
+                    arg("DeviceNumber", NUMBER),
+                    arg("Title", CHARACTER),
+                    arg("ReportCategory", NUMBER),
+                    arg("Channel", NUMBER),
+                    arg("ErrorText", CHARACTER),
+                ],
+                "Opens an output destination and sends all output that follows to it.",
+            )
+        },
+        BuiltinFunction {
+            availability: Availability::SPECFILE,
+            ..statement(
+                "OUTPUTSWITCH",
+                vec![arg("Channel", NUMBER), arg("ErrorText", CHARACTER)],
+                "Sends all output that follows to another open output destination.",
+            )
+        },
+        BuiltinFunction {
+            availability: Availability::SPECFILE,
+            ..statement(
+                "OUTPUTCLOSE",
+                vec![arg("Channel", NUMBER), arg("ErrorText", CHARACTER)],
+                "Closes an output destination opened by OUTPUTOPEN.",
+            )
+        },
+    ]
+}
+
+fn printer_functions() -> Vec<BuiltinFunction> {
+    vec![
+        statement(
+            "HPBOXDRAW",
+            vec![
+                arg("X1", NUMBER),
+                arg("Y1", NUMBER),
+                arg("X2", NUMBER),
+                arg("Y2", NUMBER),
+                arg("BoxType", NUMBER),
+                arg("BoxStyle", NUMBER),
+            ],
+            "Draws a box between two corners on a laser printer.",
+        ),
+        call(
+            "HPDESC",
+            vec![arg("HelpFile", NUMBER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the description of a help file number.",
+        ),
+        BuiltinFunction {
+            syntax: Syntax::Prefix,
+            ..statement(
+                "HPESC",
+                vec![arg("Sequence", CHARACTER)],
+                "Sends an escape sequence to a laser printer.",
+            )
+        },
+        statement(
+            "HPFONT",
+            vec![arg("Font", NUMBER), arg("PointSize", NUMBER)],
+            "Changes the font and point size of a laser printer.",
+        ),
+        statement(
+            "HPLINEDRAW",
+            vec![
+                arg("X1", NUMBER),
+                arg("Y1", NUMBER),
+                arg("X2", NUMBER),
+                arg("Y2", NUMBER),
+                arg("Width", NUMBER),
+            ],
+            "Draws a line of the given width between two points on a laser printer.",
+        ),
+        statement(
+            "HPLINESPERINCH",
+            vec![arg("LinesPerInch", NUMBER)],
+            "Changes how many lines of text a laser printer fits in a vertical inch.",
+        ),
+        keyword("HPRESET", "Resets a laser printer to its default settings."),
+        statement(
+            "HPSETUP",
+            vec![
+                choice("PageSize", &["0", "1"]),
+                choice("Orientation", &["0", "1"]),
+            ],
+            "Changes the page size, letter or legal, and the orientation, portrait or landscape, of a laser printer.",
+        ),
+        statement(
+            "HPUNDERLINE",
+            vec![choice("Mode", &["0", "1"])],
+            "Turns underlining on a laser printer on or off.",
+        ),
+        statement(
+            "HPXPOS",
+            vec![arg("X", NUMBER)],
+            "Moves a laser printer to a horizontal position.",
+        ),
+        statement(
+            "HPYPOS",
+            vec![arg("Y", NUMBER)],
+            "Moves a laser printer to a vertical position.",
+        ),
+    ]
+    .into_iter()
+    .map(|function| BuiltinFunction {
+        availability: Availability::PRINTED,
+        ..function
+    })
+    .collect()
+}
+
+fn file_functions() -> Vec<BuiltinFunction> {
+    vec![
+        statement(
+            "FILEOPEN",
+            vec![
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("OpenMode", CHARACTER),
+                arg("FileNumber", NUMBER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Opens a file of the given type and stores its number in FileNumber for the other file functions.",
+        ),
+        statement(
+            "FILECLOSE",
+            vec![arg("FileNumber", NUMBER), arg("ErrorText", CHARACTER)],
+            "Closes a file opened by FILEOPEN.",
+        ),
+        statement(
+            "FILECREATE",
+            vec![
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Creates a letter file, help file, specfile or edit file. Open it with FILEOPEN to use it.",
+        ),
+        statement(
+            "FILEDELETE",
+            vec![
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Deletes a file.",
+        ),
+        statement(
+            "FILEREAD",
+            vec![
+                arg("FileNumber", NUMBER),
+                arg("Length", NUMBER),
+                arg("Text", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Reads up to the given number of characters from an open file into Text.",
+        ),
+        statement(
+            "FILEREADLINE",
+            vec![
+                arg("FileNumber", NUMBER),
+                arg("Text", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Reads the next line of an open file into Text.",
+        ),
+        statement(
+            "FILEWRITE",
+            vec![
+                arg("FileNumber", NUMBER),
+                arg("Text", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Writes text to an open file at the current position.",
+        ),
+        statement(
+            "FILEWRITELINE",
+            vec![
+                arg("FileNumber", NUMBER),
+                arg("Text", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Writes a line of text to an open file at the current position.",
+        ),
+        statement(
+            "FILEGETPOS",
+            vec![
+                arg("FileNumber", NUMBER),
+                arg("Position", NUMBER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Stores the current byte position in an open file in Position.",
+        ),
+        statement(
+            "FILESETPOS",
+            vec![
+                arg("FileNumber", NUMBER),
+                arg("Position", NUMBER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Moves to a byte position in an open file.",
+        ),
+        statement(
+            "FILELISTOPEN",
+            vec![
+                arg("FileType", CHARACTER),
+                arg("Template", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Opens the list of files of a type whose names match the template.",
+        ),
+        statement(
+            "FILELISTREAD",
+            vec![arg("FileName", CHARACTER), arg("ErrorText", CHARACTER)],
+            "Reads the next file name from the open file list into FileName.",
+        ),
+        statement(
+            "FILELISTCLOSE",
+            vec![arg("ErrorText", CHARACTER)],
+            "Closes the open file list.",
+        ),
+        statement(
+            "FILEARCHIVEADD",
+            vec![
+                arg("ArchiveType", CHARACTER),
+                arg("ArchiveName", CHARACTER),
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("Options", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Adds a file to an archive.",
+        ),
+        statement(
+            "FILEARCHIVEEXTRACT",
+            vec![
+                arg("ArchiveType", CHARACTER),
+                arg("ArchiveName", CHARACTER),
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Extracts a file from an archive.",
+        ),
+        statement(
+            "FILEENCRYPT",
+            vec![
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("EncryptedFileName", CHARACTER),
+                arg("KeyFileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Encrypts a file with a key.",
+        ),
+        statement(
+            "FILEDECRYPT",
+            vec![
+                arg("FileType", CHARACTER),
+                arg("FileName", CHARACTER),
+                arg("KeyFileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Decrypts a file protected by encryption.",
+        ),
+    ]
+    .into_iter()
+    .map(|function| BuiltinFunction {
+        availability: Availability::SPECFILE,
+        ..function
+    })
+    .collect()
+}
+
+fn transfer_functions() -> Vec<BuiltinFunction> {
+    vec![
+        statement(
+            "FTPOPEN",
+            vec![
+                arg("ServerName", CHARACTER),
+                arg("Handle", NUMBER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Opens an FTP session and stores its handle in Handle.",
+        ),
+        statement(
+            "FTPLOGIN",
+            vec![
+                arg("Handle", NUMBER),
+                arg("UserName", CHARACTER),
+                arg("Password", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Logs in to the server of an FTP session.",
+        ),
+        statement(
+            "FTPCMD",
+            vec![
+                arg("Handle", NUMBER),
+                arg("Command", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Sends a command to the server of an FTP session.",
+        ),
+        statement(
+            "FTPGET",
+            vec![
+                arg("Handle", NUMBER),
+                arg("SourceFileName", CHARACTER),
+                arg("DestinationFileType", CHARACTER),
+                arg("DestinationFileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Retrieves a file from the server of an FTP session.",
+        ),
+        statement(
+            "FTPPUT",
+            vec![
+                arg("Handle", NUMBER),
+                arg("SourceFileName", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Sends a file to the server of an FTP session.",
+        ),
+        statement(
+            "FTPCLOSE",
+            vec![arg("Handle", NUMBER), arg("ErrorText", CHARACTER)],
+            "Closes an FTP session.",
+        ),
+        statement(
+            "EMAILSTART",
+            vec![
+                arg("From", CHARACTER),
+                arg("To", CHARACTER),
+                arg("Subject", CHARACTER),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Starts an email message, when the system is configured for email.",
+        ),
+        statement(
+            "EMAILLINE",
+            vec![arg("Line", CHARACTER), arg("ErrorText", CHARACTER)],
+            "Adds a line of text to the email message.",
+        ),
+        statement(
+            "EMAILSEND",
+            vec![arg("ErrorText", CHARACTER)],
+            "Sends the email message.",
+        ),
+    ]
+    .into_iter()
+    .map(|function| BuiltinFunction {
+        availability: Availability::SPECFILE,
+        ..function
+    })
+    .collect()
+}
+
+fn file_maintenance_functions() -> Vec<BuiltinFunction> {
+    vec![
+        BuiltinFunction {
+            syntax: Syntax::Statement(
+                "FMPERFORM ${1|CREATE,REVISE,DELETE|} ${2:Record} (${3:0},${4:0},${5:ErrorText})\n DO\n  $0\n END",
+            ),
+            ..statement(
+                "FMPERFORM",
+                vec![
+                    choice("Operation", &["CREATE", "REVISE", "DELETE"]),
+                    any("Record"),
+                    arg("ErrorText", CHARACTER),
+                ],
+                "Creates, revises or deletes a record. The SET statements in its block give the fields their values. If it fails, ErrorText says why.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Statement(
+                "TRANPERFORM ${1:TranCode} (${2:Locator},${3:Amount},${4:Reference},${5:ErrorText})\n DO\n  $0\n END",
+            ),
+            ..statement(
+                "TRANPERFORM",
+                vec![
+                    any("TranCode"),
+                    any("Locator"),
+                    arg("Amount", MONEY),
+                    any("Reference"),
+                    arg("ErrorText", CHARACTER),
+                ],
+                "Posts a monetary transaction. The SET statements in its block give the transaction fields their values. If it fails, ErrorText says why.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Prefix,
+            ..statement(
+                "SETWARNING",
+                vec![arg("WarningCode", NUMBER), arg("ExpirationDate", DATE)],
+                "Adds a warning code, from 1 to 999, to the record an FMPERFORM block maintains, expiring on the date.",
+            )
+        },
+        BuiltinFunction {
+            syntax: Syntax::Prefix,
+            ..statement(
+                "CLEARWARNING",
+                vec![arg("WarningCode", NUMBER)],
+                "Removes a warning code from the record an FMPERFORM block maintains.",
+            )
+        },
+        statement(
+            "VALIDATEFIELDSET",
+            vec![
+                arg("Field", CHARACTER),
+                any("Value"),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Checks a value against the rules of a field before file maintenance sets it. If it is not valid, ErrorText says why.",
+        ),
+        statement(
+            "COPYAPP",
+            vec![
+                arg("SourceAppId", NUMBER),
+                arg("DestinationAccount", CHARACTER),
+                arg("DestinationAppId", NUMBER),
+                choice("MoveFlag", &["0", "1"]),
+                choice("PersonFlag", &["0", "1"]),
+                choice("FinanceFlag", &["0", "1"]),
+                choice("TrackingFlag", &["0", "1"]),
+                choice("NoteFlag", &["0", "1"]),
+                choice("PreferenceFlag", &["0", "1"]),
+                choice("CreditReportFlag", &["0", "1"]),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Copies or moves an Application record and its child records to another ID on the same or another account, with its Credit Report records if asked.",
+        ),
+        statement(
+            "CREATEFINANCEFROMCREDREP",
+            vec![
+                choice("CheckPrivileges", &["0", "1"]),
+                arg("AppId", NUMBER),
+                arg("CreditReportLocator", NUMBER),
+                choice("SkipBlankDescription", &["0", "1"]),
+                choice("SkipZeroBalance", &["0", "1"]),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Creates Finance records for a loan application from the trade items of a credit report.",
+        ),
+    ]
+    .into_iter()
+    .map(|function| BuiltinFunction {
+        availability: Availability::SPECFILE,
+        ..function
+    })
+    .collect()
+}
+
+fn field_functions() -> Vec<BuiltinFunction> {
+    let mut functions = vec![
+        call(
+            "GETFIELDNUMBER",
+            vec![arg("RecordNumber", NUMBER), arg("Mnemonic", CHARACTER)],
+            ReturnType::Value(NUMBER),
+            "Returns the number of the field with the mnemonic, or 0 when there is none. The other GETFIELD functions take the field number.",
+        ),
+        call(
+            "GETFIELDMNEMONIC",
+            vec![
+                arg("RecordNumber", NUMBER),
+                arg("FieldNumber", NUMBER),
+                arg("SubfieldNumber", NUMBER),
+            ],
+            ReturnType::Value(CHARACTER),
+            "Returns the mnemonic of a field, or an empty string when there is none.",
+        ),
+        call(
+            "GETFIELDNAME",
+            vec![
+                arg("RecordNumber", NUMBER),
+                arg("FieldNumber", NUMBER),
+                arg("SubfieldNumber", NUMBER),
+            ],
+            ReturnType::Value(CHARACTER),
+            "Returns the name of a field, or an empty string when there is none.",
+        ),
+        call(
+            "GETFIELDHELPFILE",
+            vec![
+                arg("RecordNumber", NUMBER),
+                arg("FieldNumber", NUMBER),
+                arg("SubfieldNumber", NUMBER),
+            ],
+            ReturnType::Value(NUMBER),
+            "Returns the help file number of a field, or 0 when there is none. Pass 0 for the subfield when the field has none.",
+        ),
+        call(
+            "GETFIELDDATATYPE",
+            vec![
+                arg("RecordNumber", NUMBER),
+                arg("FieldNumber", NUMBER),
+                arg("SubfieldNumber", NUMBER),
+            ],
+            ReturnType::Value(NUMBER),
+            "Returns the data type of a field as a number, or 0 when there is no such field.",
+        ),
+        call(
+            "GETFIELDDATAMAX",
+            vec![
+                arg("RecordNumber", NUMBER),
+                arg("FieldNumber", NUMBER),
+                arg("SubfieldNumber", NUMBER),
+            ],
+            ReturnType::Value(NUMBER),
+            "Returns the largest value or length of a field, or 0 when there is no such field.",
+        ),
+    ];
+    let get_data = [
+        (
+            "GETDATACHAR",
+            CHARACTER,
+            "Returns the current value of a character field of the Parameter or Console file. GETDATACHARACTER is the same function.",
+        ),
+        (
+            "GETDATADATE",
+            DATE,
+            "Returns the current value of a date field of the Parameter or Console file.",
+        ),
+        (
+            "GETDATAMONEY",
+            MONEY,
+            "Returns the current value of a money field of the Parameter or Console file.",
+        ),
+        (
+            "GETDATANUMBER",
+            NUMBER,
+            "Returns the current value of a numeric field of the Parameter or Console file.",
+        ),
+        (
+            "GETDATARATE",
+            RATE,
+            "Returns the current value of a rate field of the Parameter or Console file.",
+        ),
+    ];
+    for (name, data_type, description) in get_data {
+        functions.push(call(
+            name,
+            vec![
+                arg("InfoCode", NUMBER),
+                optional(any("Type1")),
+                optional(any("Type2")),
+                optional(any("Type3")),
+                optional(any("Type4")),
+            ],
+            ReturnType::Value(data_type),
+            description,
+        ));
+    }
+    functions
+}
+
+fn other_functions() -> Vec<BuiltinFunction> {
+    vec![
+        call(
+            "ANYSERVICE",
+            vec![
+                choice("Record", SERVICE_RECORDS),
+                arg("ServiceCode", NUMBER),
+            ],
+            ReturnType::Boolean,
+            "Is TRUE when the current record of the type has the service code, from 1 to 99.",
+        ),
+        call(
+            "ANYWARNING",
+            vec![
+                choice("Record", WARNING_RECORDS),
+                arg("WarningCode", NUMBER),
+            ],
+            ReturnType::Boolean,
+            "Is TRUE when the current record of the type has the warning code, from 1 to 999.",
+        ),
+        call(
+            "SYSUSERNAME",
+            vec![arg("UserNumber", NUMBER)],
+            ReturnType::Value(CHARACTER),
+            "Returns the name of the user with the number.",
+        ),
+        call(
+            "FLOATVALUE",
+            vec![
+                arg("Text", CHARACTER),
+                arg("Result", FLOAT),
+                arg("ErrorPosition", NUMBER),
+            ],
+            ReturnType::Nothing,
+            "Converts a character string to a floating point value and stores it in Result. If the string is not a number, ErrorPosition is where it goes wrong.",
+        ),
+        statement(
+            "EXECUTE",
+            vec![arg("Specfile", CHARACTER), arg("ErrorText", CHARACTER)],
+            "Runs a subroutine specfile. It shares the variables of the calling specfile that it declares.",
+        ),
+        statement(
+            "INITSUBROUTINE",
+            vec![arg("ErrorText", CHARACTER)],
+            "Sets up a subroutine specfile run by EXECUTE.",
+        ),
+        keyword("TERMINATE", "Stops the specfile."),
+        BuiltinFunction {
+            syntax: Syntax::Assignment,
+            ..statement(
+                "WHILELIMIT",
+                vec![arg("Limit", NUMBER)],
+                "Sets how many times a WHILE loop may repeat before the specfile stops with an error.",
+            )
+        },
+        statement(
+            "DIVPROJECTINIT",
+            vec![choice("DataSource", &["0", "1", "2"]), any("DefaultType")],
+            "Sets up the variables for annual percentage yield calculations and dividend projections.",
+        ),
+        statement(
+            "LOANPROJECTINIT",
+            vec![any("DataSource"), any("DefaultType")],
+            "Sets up the variables for loan projection calculations.",
+        ),
+        keyword(
+            "OVERDRAWAVAILABLEINIT",
+            "Sets up the variables for calculating the funds available for overdrafts.",
+        ),
+        keyword(
+            "OVERDRAWAVAILABLECALC",
+            "Calculates the funds available for overdrafts with the variables OVERDRAWAVAILABLEINIT set up.",
+        ),
+        statement(
+            "INITCREDITREPORT",
+            vec![any("SourceType")],
+            "Sets up the variables for pulling credit reports from Equifax, Experian, TransUnion and the ChexSystems suite.",
+        ),
+        keyword(
+            "PULLCREDITREPORT",
+            "Pulls a credit report from a credit bureau with the variables INITCREDITREPORT set up.",
+        ),
+        statement(
+            "QUEUECREDITREPORT",
+            vec![
+                any("Bureau"),
+                any("Account"),
+                any("Locator"),
+                arg("ErrorText", CHARACTER),
+            ],
+            "Queues a credit report to be pulled in the background.",
+        ),
+    ]
+}
+
+/// A function called as `NAME(arguments)`, available everywhere.
+fn call(
+    name: &'static str,
+    parameters: Vec<Parameter>,
+    returns: ReturnType,
+    description: &'static str,
+) -> BuiltinFunction {
+    BuiltinFunction {
+        name,
+        syntax: Syntax::Call,
+        parameters,
+        returns,
+        availability: Availability::ANYWHERE,
+        deprecated: None,
+        description,
+    }
+}
+
+/// A call that returns nothing.
+fn statement(
+    name: &'static str,
+    parameters: Vec<Parameter>,
+    description: &'static str,
+) -> BuiltinFunction {
+    call(name, parameters, ReturnType::Nothing, description)
+}
+
+/// A statement written as its name alone.
+fn keyword(name: &'static str, description: &'static str) -> BuiltinFunction {
+    BuiltinFunction {
+        syntax: Syntax::Keyword,
+        ..statement(name, vec![], description)
+    }
+}
+
+fn arg(name: &'static str, data_type: DataType) -> Parameter {
+    Parameter {
+        data_type: Some(data_type),
+        ..any(name)
+    }
+}
+
+/// A parameter that takes a value of any type.
+fn any(name: &'static str) -> Parameter {
+    Parameter {
+        name,
+        data_type: None,
+        choices: &[],
+        optional: false,
+    }
+}
+
+fn choice(name: &'static str, choices: &'static [&'static str]) -> Parameter {
+    Parameter {
+        choices,
+        ..any(name)
+    }
+}
+
+fn optional(parameter: Parameter) -> Parameter {
+    Parameter {
+        optional: true,
+        ..parameter
+    }
+}
+
+#[test]
+fn test_builtin_functions() {
+    let functions = builtin_functions();
+    assert_eq!(functions.len(), BUILTIN_FUNCTIONS.len(), "duplicate names");
+
+    let segment = &BUILTIN_FUNCTIONS["SEGMENT"];
+    assert_eq!(segment.signature(), "SEGMENT(Text,Start,End)");
+    assert_eq!(
+        segment.snippet(),
+        "SEGMENT(${1:Text},${2:Start},${3:End})$0"
+    );
+    assert_eq!(segment.returns, ReturnType::Value(DataType::Character));
+
+    let enter_date = &BUILTIN_FUNCTIONS["ENTERDATE"];
+    assert_eq!(enter_date.signature(), "ENTERDATE(Prompt,[Default])");
+    assert_eq!(enter_date.snippet(), "ENTERDATE(${1:Prompt})$0");
+    assert_eq!(enter_date.availability, Availability::INTERACTIVE);
+    assert_eq!(
+        BUILTIN_FUNCTIONS["DATEREAD"].deprecated,
+        Some("Use ENTERDATE.")
+    );
+    assert_eq!(BUILTIN_FUNCTIONS["COL"].signature(), "COL=Column");
+    assert_eq!(
+        BUILTIN_FUNCTIONS["ANYSERVICE"].snippet(),
+        "ANYSERVICE(${1|SHARE,LOAN,CARD,EXTERNALLOAN|},${2:ServiceCode})$0"
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["FMPERFORM"].signature(),
+        "FMPERFORM CREATE|REVISE|DELETE Record (0,0,ErrorText)"
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["AFTERLAST"].signature(),
+        "AFTERLAST(Text,Search)"
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["BEFOREFIRST"].returns,
+        ReturnType::Value(DataType::Character)
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["SETWARNING"].signature(),
+        "SETWARNING WarningCode ExpirationDate"
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["CLEARWARNING"].snippet(),
+        "CLEARWARNING ${1:WarningCode}$0"
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["FORMLENGTH"].signature(),
+        "FORMLENGTH=Lines"
+    );
+    assert_eq!(
+        BUILTIN_FUNCTIONS["DIALOGSTART"].availability.describe(),
+        "Available in windows"
+    );
+    assert_eq!(
+        Availability::PRINTED.describe(),
+        "Available on demand, in batch and in letters"
+    );
+}
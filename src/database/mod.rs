@@ -1,3 +1,5 @@
 pub mod account_record_fields;
+pub mod functions;
+pub mod record_types;
+pub mod system_variables;
 pub mod types;
-
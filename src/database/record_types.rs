@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use super::{account_record_fields::ACCOUNT_RECORD_FIELDS, types::DatabaseField};
+
+/// Every record type PowerOn accepts, as the host spells it. Subrecords are
+/// named after their parent, such as `SHARE TRANSFER`.
+pub const RECORD_TYPES: &[&str] = &[
+    "ACCESS",
+    "ACCOUNT",
+    "ACHADDENDA",
+    "ACHADDINFO",
+    "ACHEDIT",
+    "ACHITEM",
+    "ACTIVITY",
+    "AGREEMENT",
+    "AGREEMENT TRANSACTION",
+    "ATMDIALOG",
+    "BATCHACHORIG",
+    "BILL",
+    "CARD",
+    "CARD ACCESS",
+    "CARD NAME",
+    "CARD NOTE",
+    "CASHLETTOR",
+    "CASHORDER",
+    "CDMDIALOG",
+    "CHECK",
+    "CHECKORDER",
+    "COLLATERAL",
+    "COLLATERAL COLLHOLD",
+    "COLLATERAL DOCUMENT",
+    "COMMENT",
+    "CORPTRANSFER",
+    "CPWORKCARD",
+    "CPWORKCARD NOTE",
+    "CPWORKCARD TRACKING",
+    "CREDREP",
+    "CREDREP ITEM",
+    "CTR",
+    "CTRACCOUNT",
+    "CTRBRANCH",
+    "CTRFOREIGN",
+    "CTRPERSON",
+    "CTRPERSON CTRTRANINFO",
+    "DEALER",
+    "EFT",
+    "EFT ADDENDAINFO",
+    "EFT NAME",
+    "EFT TRANSFER",
+    "ESCROW",
+    "ESCROWANALYSIS",
+    "EXCPADDENDA",
+    "EXCPADDINFO",
+    "EXCPITEM",
+    "EXTERNALACCOUNT",
+    "EXTERNALLOAN",
+    "EXTERNALLOAN NAME",
+    "EXTERNALLOAN NOTE",
+    "EXTERNALLOAN TRACKING",
+    "EXTERNALLOAN TRANSFER",
+    "FINANCE",
+    "FMHISTORY",
+    "GLACCOUNT",
+    "GLSUBACCOUNT",
+    "GLTRAN",
+    "HOLD",
+    "HOUSEHOLD",
+    "INVENTORY",
+    "INVOICE",
+    "IRA",
+    "IRS",
+    "IRS DISTRIBUTION",
+    "IRS NAME",
+    "LOAN",
+    "LOAN BANKRUPTCY",
+    "LOAN BANKRUPTCY PREPETITIONBAL",
+    "LOAN CHECKORDER",
+    "LOAN ESCROW",
+    "LOAN ESCROWANALYSIS",
+    "LOAN HOLD",
+    "LOAN LNSEGMENT",
+    "LOAN NAME",
+    "LOAN NOTE",
+    "LOAN PLEDGE",
+    "LOAN PLEDGE NAME",
+    "LOAN RATECHANGE",
+    "LOAN SCHEDULE",
+    "LOAN TRACKING",
+    "LOAN TRANSACTION",
+    "LOAN TRANSFER",
+    "LOANAPP",
+    "LOANAPP ESCROW",
+    "LOANAPP ESCROWANALYSIS",
+    "LOANAPP FINANCE",
+    "LOANAPP LNSEGMENT",
+    "LOANAPP NOTE",
+    "LOANAPP PERSON",
+    "LOANAPP PLEDGE",
+    "LOANAPP SCHEDULE",
+    "LOANAPP TRACKING",
+    "LOOKUP",
+    "MBRADDRESS",
+    "MEMBERREC",
+    "NAME",
+    "NONACCTNAME",
+    "NOTE",
+    "OFACDETAILS",
+    "PARTICIPANT",
+    "PARTICIPANT TRANSACTION",
+    "PARTICIPATION",
+    "PARTICIPATIONLOAN",
+    "PAYEE",
+    "PAYROLL",
+    "PERSON",
+    "PLEDGE",
+    "POOL",
+    "POOLLOAN",
+    "PORTFOLIO",
+    "PORTFOLIO HOLD",
+    "PORTFOLIO NOTE",
+    "PORTFOLIO TRACKING",
+    "PREFERENCE",
+    "PREFERENCE ACCESS",
+    "RECEIVEDITEM",
+    "REMITTANCE",
+    "RESERVEDPLAN",
+    "RESERVEDPLAN LOAN",
+    "RESERVEDPLAN TRANSACTION",
+    "SAVINGS",
+    "SHARE",
+    "SHARE ANALYSIS",
+    "SHARE ANALYSISGROUP",
+    "SHARE ANALYSISPLAN",
+    "SHARE CHECKORDER",
+    "SHARE HOLD",
+    "SHARE NAME",
+    "SHARE NOTE",
+    "SHARE TRACKING",
+    "SHARE TRANSACTION",
+    "SHARE TRANSFER",
+    "SITE",
+    "SITE CASHORDERTYPE",
+    "TRACKING",
+    "TRANSACTION",
+    "TRANSFER",
+    "USER",
+    "VENDOR",
+    "WESTERNUNION",
+    "WIRE",
+    "WIRE BENEFICIARYADV",
+    "WIRE BENEFICIARYFIADV",
+    "WIRE BENEFICIARYFIINFO",
+    "WIRE BENEFICIARYINFO",
+    "WIRE DRAWDOWNDEBITACCTADV",
+    "WIRE FITOFIINFO",
+    "WIRE INTERMEDFIADV",
+    "WIRE INTERMEDFIINFO",
+    "WIRE RECEIVERFIINFO",
+    "WIRE SERVICEMESSAGE",
+    "WIRE USCAUDITINFO",
+    "WORKLISTEDIT",
+    "WORKLISTEDIT WORKLISTFIELD",
+];
+
+/// Records of one word that belong to an account. Every other record that
+/// belongs to another is named after its parent, such as `SHARE TRANSFER`
+/// under `SHARE`, and the records left over stand alone.
+const ACCOUNT_SUBRECORDS: &[&str] = &[
+    "CARD",
+    "COMMENT",
+    "EFT",
+    "EXTERNALLOAN",
+    "IRS",
+    "LOAN",
+    "LOANAPP",
+    "NAME",
+    "NOTE",
+    "PREFERENCE",
+    "SHARE",
+    "TRACKING",
+];
+
+/// The record `record` belongs to, or None for a record that stands alone.
+pub fn parent_record(record: &str) -> Option<String> {
+    let record = normalize_record_type(record);
+    if ACCOUNT_SUBRECORDS.contains(&record.as_str()) {
+        return Some("ACCOUNT".to_string());
+    }
+    let (parent, _) = record.rsplit_once(' ')?;
+    is_record_type(parent).then(|| parent.to_string())
+}
+
+/// The records that belong directly to `record`.
+pub fn child_records(record: &str) -> Vec<&'static str> {
+    let record = normalize_record_type(record);
+    RECORD_TYPES
+        .iter()
+        .copied()
+        .filter(|child| parent_record(child).as_deref() == Some(record.as_str()))
+        .collect()
+}
+
+/// `record` followed by the records it belongs to, nearest first.
+pub fn record_ancestry(record: &str) -> Vec<String> {
+    let mut ancestry = vec![normalize_record_type(record)];
+    while let Some(parent) = ancestry.last().and_then(|record| parent_record(record)) {
+        ancestry.push(parent);
+    }
+    ancestry
+}
+
+/// The field catalog of `record`, for the records that have one.
+pub fn record_fields(record: &str) -> Option<&'static HashMap<&'static str, DatabaseField>> {
+    match normalize_record_type(record).as_str() {
+        "ACCOUNT" => Some(&ACCOUNT_RECORD_FIELDS),
+        _ => None,
+    }
+}
+
+/// The catalog spelling of `name`, which may use any case and spacing.
+pub fn normalize_record_type(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+pub fn is_record_type(name: &str) -> bool {
+    RECORD_TYPES.contains(&normalize_record_type(name).as_str())
+}
+
+#[test]
+fn test_record_types() {
+    assert!(is_record_type("account"));
+    assert!(is_record_type("Share  Transfer"));
+    assert!(!is_record_type("SHARES"));
+}
+
+#[test]
+fn test_record_hierarchy() {
+    assert_eq!(parent_record("share transfer").as_deref(), Some("SHARE"));
+    assert_eq!(parent_record("SHARE").as_deref(), Some("ACCOUNT"));
+    assert_eq!(parent_record("ACCOUNT"), None);
+    assert_eq!(parent_record("GLACCOUNT"), None);
+    assert_eq!(
+        record_ancestry("LOAN PLEDGE NAME"),
+        vec!["LOAN PLEDGE NAME", "LOAN PLEDGE", "LOAN", "ACCOUNT"]
+            .into_iter()
+            .map(|record| record.to_string())
+            .collect::<Vec<String>>()
+    );
+    let loan = child_records("LOAN");
+    assert!(loan.contains(&"LOAN TRACKING"));
+    assert!(!loan.contains(&"LOAN PLEDGE NAME"));
+    assert!(child_records("ACCOUNT").contains(&"NAME"));
+
+    // Every record named after another belongs to it.
+    for record in RECORD_TYPES.iter().filter(|record| record.contains(' ')) {
+        assert!(parent_record(record).is_some(), "{} has no parent", record);
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+
+use super::types::{DataType, SystemVariable};
+
+lazy_static! {
+    /// System variables keyed by their name without the `@`.
+    pub static ref SYSTEM_VARIABLES: HashMap<String, SystemVariable> = load_system_variables();
+}
+
+/// How many variables each `@USER` family holds.
+const USER_FAMILY_SIZE: u32 = 5;
+
+fn load_system_variables() -> HashMap<String, SystemVariable> {
+    let mut variables = vec![
+        variable(
+            "SYSTEMDATE",
+            DataType::Date,
+            "The system date of the credit union, which is the posting date rather than the calendar date.",
+        ),
+        variable(
+            "SYSTEMTIME",
+            DataType::Number,
+            "The time of day the specfile read it, as a number in HHMM format.",
+        ),
+        variable(
+            "USERNUMBER",
+            DataType::Number,
+            "The number of the user running the specfile.",
+        ),
+        variable(
+            "ENVARGCHAR",
+            DataType::Character,
+            "The character argument passed to the specfile by the application that ran it.",
+        ),
+        variable(
+            "ENVARGNUMBER",
+            DataType::Number,
+            "The number argument passed to the specfile by the application that ran it.",
+        ),
+        variable(
+            "ENVARGDATE",
+            DataType::Date,
+            "The date argument passed to the specfile by the application that ran it.",
+        ),
+        variable(
+            "ENVARGMONEY",
+            DataType::Money,
+            "The money argument passed to the specfile by the application that ran it.",
+        ),
+        variable(
+            "ENVARGRATE",
+            DataType::Rate,
+            "The rate argument passed to the specfile by the application that ran it.",
+        ),
+    ];
+
+    let user_families = [
+        ("USERCHR", DataType::Character, "character"),
+        ("USERNUM", DataType::Number, "number"),
+        ("USERCODE", DataType::Code, "code"),
+        ("USERDATE", DataType::Date, "date"),
+        ("USERAMT", DataType::Money, "money"),
+        ("USERRATE", DataType::Rate, "rate"),
+    ];
+    for (prefix, data_type, kind) in user_families {
+        for n in 1..=USER_FAMILY_SIZE {
+            variables.push(SystemVariable {
+                name: format!("{}{}", prefix, n),
+                data_type,
+                description: format!(
+                    "General purpose {} variable {} of the user running the specfile. It keeps its value between specfiles run in the same session.",
+                    kind, n
+                ),
+            });
+        }
+    }
+
+    variables
+        .into_iter()
+        .map(|variable| (variable.name.clone(), variable))
+        .collect()
+}
+
+fn variable(name: &str, data_type: DataType, description: &str) -> SystemVariable {
+    SystemVariable {
+        name: name.to_string(),
+        data_type,
+        description: description.to_string(),
+    }
+}
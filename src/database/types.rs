@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Clone)]
 pub struct DatabaseField {
     pub field_number: u32,
@@ -11,7 +13,275 @@ pub struct DatabaseField {
     pub length: Option<u32>,
 }
 
+impl DatabaseField {
+    /// The type as the field catalog writes it, such as `10 Characters`.
+    pub fn type_description(&self) -> String {
+        match (self.data_type, self.length) {
+            (DataType::Character, Some(length)) => format!("{} Characters", length),
+            (DataType::Code, Some(length)) => format!("Code to {}", length),
+            (data_type, _) => data_type.as_str().to_string(),
+        }
+    }
+
+    /// Who enters the field's value, such as `User-entered`, from the
+    /// `Source` line of the details.
+    pub fn source(&self) -> Option<&'static str> {
+        self.details.lines().find_map(|line| {
+            let source = line.trim().strip_prefix("Source")?;
+            Some(source.trim_start_matches(':').trim())
+        })
+    }
+
+    /// Whether only the host sets the field, so file maintenance cannot.
+    pub fn is_system_entered(&self) -> bool {
+        self.source()
+            .is_some_and(|source| source.starts_with("System") && !source.contains("User-entered"))
+    }
+}
+
+/// A variable the host provides, written with a leading `@`.
+#[derive(Debug, Clone)]
+pub struct SystemVariable {
+    /// The name without the `@`.
+    pub name: String,
+    pub data_type: DataType,
+    pub description: String,
+}
+
+/// A function built into PowerOn, such as `SEGMENT` or `ENTERDATE`.
+#[derive(Debug, Clone)]
+pub struct BuiltinFunction {
+    pub name: &'static str,
+    pub syntax: Syntax,
+    pub parameters: Vec<Parameter>,
+    pub returns: ReturnType,
+    pub availability: Availability,
+    /// What to use instead, when the function is deprecated.
+    pub deprecated: Option<&'static str>,
+    pub description: &'static str,
+}
+
+impl BuiltinFunction {
+    /// How a call is written, such as `SEGMENT(Text,Start,End)`. Optional
+    /// parameters are in brackets.
+    pub fn signature(&self) -> String {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|parameter| {
+                if parameter.optional {
+                    format!("[{}]", parameter.name)
+                } else {
+                    parameter.name.to_string()
+                }
+            })
+            .collect();
+        match self.syntax {
+            Syntax::Call => format!("{}({})", self.name, parameters.join(",")),
+            Syntax::Prefix => format!("{} {}", self.name, parameters.join(" ")),
+            Syntax::Assignment => format!("{}={}", self.name, parameters.join(" ")),
+            Syntax::Keyword => self.name.to_string(),
+            Syntax::Block => format!("{} ... END", self.name),
+            Syntax::Statement(snippet) => {
+                snippet_signature(snippet.lines().next().unwrap_or_default())
+            }
+        }
+    }
+
+    /// The snippet completion inserts, with a tab stop for every required
+    /// parameter and the choices of those that have them.
+    pub fn snippet(&self) -> String {
+        let placeholders: Vec<String> = self
+            .parameters
+            .iter()
+            .filter(|parameter| !parameter.optional)
+            .enumerate()
+            .map(|(i, parameter)| match parameter.choices {
+                [] => format!("${{{}:{}}}", i + 1, parameter.name),
+                choices => format!("${{{}|{}|}}", i + 1, choices.join(",")),
+            })
+            .collect();
+        match self.syntax {
+            Syntax::Call => format!("{}({})$0", self.name, placeholders.join(",")),
+            Syntax::Prefix => format!("{} {}$0", self.name, placeholders.join(" ")),
+            Syntax::Assignment => format!("{}={}$0", self.name, placeholders.join(" ")),
+            Syntax::Keyword => self.name.to_string(),
+            Syntax::Block => format!("{}\n\t$0\nEND", self.name),
+            Syntax::Statement(snippet) => snippet.to_string(),
+        }
+    }
+
+    /// The signature, what the function returns, its description, where it
+    /// runs and whether it is deprecated, as Markdown.
+    pub fn documentation(&self) -> String {
+        let mut documentation = format!("```poweron\n{}\n```", self.signature());
+        if let Some(returns) = self.returns.describe() {
+            documentation.push_str(&format!("\nReturns {}.", returns));
+        }
+        documentation.push_str("\n\n");
+        documentation.push_str(self.description);
+        let typed: Vec<String> = self
+            .parameters
+            .iter()
+            .filter_map(|parameter| {
+                let data_type = parameter.data_type?;
+                Some(format!("{} is {}", parameter.name, data_type.as_str()))
+            })
+            .collect();
+        if !typed.is_empty() {
+            documentation.push_str(&format!("\n\n{}.", typed.join(", ")));
+        }
+        documentation.push_str(&format!("\n\n{}.", self.availability.describe()));
+        if let Some(replacement) = self.deprecated {
+            documentation.push_str(&format!("\n\n**Deprecated.** {}", replacement));
+        }
+        documentation
+    }
+}
+
+/// The signature a snippet spells out: placeholders become their text,
+/// choices their options, and tab stops disappear.
+fn snippet_signature(snippet: &str) -> String {
+    let mut signature = String::new();
+    let mut chars = snippet.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('$', Some('{')) => {
+                chars.next();
+                let placeholder: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                let placeholder = placeholder.trim_start_matches(|c: char| c.is_ascii_digit());
+                match placeholder.strip_prefix(':') {
+                    Some(text) => signature.push_str(text),
+                    None => signature.push_str(&placeholder.trim_matches('|').replace(',', "|")),
+                }
+            }
+            ('$', Some(c)) if c.is_ascii_digit() => {
+                while chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    chars.next();
+                }
+            }
+            _ => signature.push(c),
+        }
+    }
+    signature.trim().to_string()
+}
+
+/// How a builtin is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// `NAME(arguments)`.
+    Call,
+    /// `NAME argument`, such as `PRINT`.
+    Prefix,
+    /// `NAME=argument`, such as `COL=`.
+    Assignment,
+    /// The name alone.
+    Keyword,
+    /// `NAME`, lines of output, then `END`.
+    Block,
+    /// A statement with a shape of its own, given as its snippet.
+    Statement(&'static str),
+}
+
 #[derive(Debug, Clone)]
+pub struct Parameter {
+    pub name: &'static str,
+    /// The type the argument must have, or None when any type will do.
+    pub data_type: Option<DataType>,
+    /// The values the argument is written as, when there are few of them.
+    pub choices: &'static [&'static str],
+    pub optional: bool,
+}
+
+/// What a builtin evaluates to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnType {
+    /// Nothing: the builtin is a statement.
+    Nothing,
+    /// TRUE or FALSE, for use in conditions.
+    Boolean,
+    Value(DataType),
+    /// The type of the first argument, as for `ABS`.
+    Argument,
+}
+
+impl ReturnType {
+    /// What the builtin returns, in words, or None when it returns nothing.
+    pub fn describe(&self) -> Option<String> {
+        match self {
+            ReturnType::Nothing => None,
+            ReturnType::Boolean => Some("TRUE or FALSE".to_string()),
+            ReturnType::Value(data_type) => Some(data_type.as_str().to_string()),
+            ReturnType::Argument => Some("the type of its argument".to_string()),
+        }
+    }
+}
+
+/// Where a builtin can be used: in specfiles run on demand or in batch, in
+/// interactive windows, and in letter specfiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Availability {
+    pub demand: bool,
+    pub batch: bool,
+    pub window: bool,
+    pub letter: bool,
+}
+
+impl Availability {
+    pub const ANYWHERE: Availability = Availability {
+        demand: true,
+        batch: true,
+        window: true,
+        letter: true,
+    };
+    /// Prompts need someone to answer them, so batch runs cannot use them.
+    pub const INTERACTIVE: Availability = Availability {
+        demand: true,
+        batch: false,
+        window: true,
+        letter: false,
+    };
+    pub const WINDOW: Availability = Availability {
+        demand: false,
+        batch: false,
+        window: true,
+        letter: false,
+    };
+    /// Printer control, for reports and letters rather than the screen.
+    pub const PRINTED: Availability = Availability {
+        demand: true,
+        batch: true,
+        window: false,
+        letter: true,
+    };
+    /// Everywhere a specfile runs, but not in letters.
+    pub const SPECFILE: Availability = Availability {
+        demand: true,
+        batch: true,
+        window: true,
+        letter: false,
+    };
+
+    /// Such as `Available on demand and in batch`.
+    pub fn describe(&self) -> String {
+        let places: Vec<&str> = [
+            (self.demand, "on demand"),
+            (self.batch, "in batch"),
+            (self.window, "in windows"),
+            (self.letter, "in letters"),
+        ]
+        .into_iter()
+        .filter_map(|(available, place)| available.then_some(place))
+        .collect();
+        match places.split_last() {
+            None => "Not available".to_string(),
+            Some((last, [])) => format!("Available {}", last),
+            Some((last, rest)) => format!("Available {} and {}", rest.join(", "), last),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DataType {
     Character,
     Code,
@@ -22,15 +292,76 @@ pub enum DataType {
     Rate,
 }
 
-#[derive(Debug)]
-pub enum RecordType {
-    Account,
-}
+impl DataType {
+    pub const ALL: [DataType; 7] = [
+        DataType::Character,
+        DataType::Code,
+        DataType::Date,
+        DataType::Float,
+        DataType::Money,
+        DataType::Number,
+        DataType::Rate,
+    ];
 
-impl RecordType {
     pub fn as_str(&self) -> &'static str {
         match self {
-            RecordType::Account => "account",
+            DataType::Character => "CHARACTER",
+            DataType::Code => "CODE",
+            DataType::Date => "DATE",
+            DataType::Float => "FLOAT",
+            DataType::Money => "MONEY",
+            DataType::Number => "NUMBER",
+            DataType::Rate => "RATE",
+        }
+    }
+
+    pub fn from_name(data_type: &str) -> Option<Self> {
+        match data_type.trim().to_uppercase().as_str() {
+            "CHARACTER" => Some(DataType::Character),
+            "CODE" => Some(DataType::Code),
+            "DATE" => Some(DataType::Date),
+            "FLOAT" => Some(DataType::Float),
+            "MONEY" => Some(DataType::Money),
+            "NUMBER" => Some(DataType::Number),
+            "RATE" => Some(DataType::Rate),
+            _ => None,
         }
     }
+
+    /// Whether a value of this type can be assigned to or compared with one
+    /// of `other` without conversion. Codes are small numbers.
+    pub fn is_compatible_with(&self, other: DataType) -> bool {
+        matches!(
+            (self, other),
+            (DataType::Code, DataType::Number) | (DataType::Number, DataType::Code)
+        ) || *self == other
+    }
+}
+
+#[test]
+fn test_field_source() {
+    use super::account_record_fields::ACCOUNT_RECORD_FIELDS;
+
+    let number = &ACCOUNT_RECORD_FIELDS["number"];
+    assert_eq!(number.source(), Some("System-entered"));
+    assert!(number.is_system_entered());
+    let branch = &ACCOUNT_RECORD_FIELDS["branch"];
+    assert_eq!(branch.source(), Some("User-entered"));
+    assert!(!branch.is_system_entered());
+}
+
+#[test]
+fn test_snippet_signature() {
+    assert_eq!(
+        snippet_signature("ABS(${1:expression})$0"),
+        "ABS(expression)"
+    );
+    assert_eq!(
+        snippet_signature("ANYSERVICE(${1|SHARE,LOAN|},${2:<1-99>})$0"),
+        "ANYSERVICE(SHARE|LOAN,<1-99>)"
+    );
+    assert_eq!(
+        snippet_signature("CHARACTERREAD(\"${1:Prompt}\")$0"),
+        "CHARACTERREAD(\"Prompt\")"
+    );
 }
@@ -0,0 +1,108 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::{error, info};
+use tower_lsp::Client;
+
+use crate::{
+    cancellation::CancellationToken, panics::report_panic, state::ServerState, sync::lock,
+};
+
+use super::collect_diagnostics;
+
+/// Publishes diagnostics a short while after the last edit rather than
+/// after every edit, so rapid typing does not queue a full analysis per
+/// keystroke. Each new request cancels the pending run and takes over the
+/// files it had not published yet.
+#[derive(Debug, Default)]
+pub struct DiagnosticsScheduler {
+    pending: Mutex<PendingDiagnostics>,
+}
+
+#[derive(Debug, Default)]
+struct PendingDiagnostics {
+    token: CancellationToken,
+    uris: Vec<String>,
+}
+
+impl DiagnosticsScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn schedule(
+        self: &Arc<Self>,
+        client: &Client,
+        state: &Arc<ServerState>,
+        uris: Vec<String>,
+    ) {
+        let token = {
+            let mut pending = lock(&self.pending);
+            pending.token.cancel();
+            pending.token = state.shutdown.child();
+            for uri in uris {
+                if !pending.uris.contains(&uri) {
+                    pending.uris.push(uri);
+                }
+            }
+            pending.token.clone()
+        };
+
+        let scheduler = Arc::clone(self);
+        let client = client.clone();
+        let state = Arc::clone(state);
+        tokio::spawn(async move {
+            scheduler.run(client, state, token).await;
+        });
+    }
+
+    async fn run(&self, client: Client, state: Arc<ServerState>, token: CancellationToken) {
+        let snapshot = state.snapshot();
+        let debounce = Duration::from_millis(snapshot.settings.diagnostics.debounce);
+        tokio::time::sleep(debounce).await;
+        if token.is_cancelled() {
+            return;
+        }
+
+        let uris = lock(&self.pending).uris.clone();
+        let snapshot = state.snapshot();
+        let task_token = token.clone();
+        let diagnostics =
+            tokio::task::spawn_blocking(move || collect_diagnostics(&snapshot, &uris, &task_token))
+                .await;
+        let diagnostics = match diagnostics {
+            Ok(Ok(diagnostics)) => diagnostics,
+            Ok(Err(_)) => {
+                info!("Diagnostics cancelled by a newer edit");
+                return;
+            }
+            Err(e) => {
+                match e.try_into_panic() {
+                    Ok(payload) => report_panic(&client, &state, "diagnostics", payload).await,
+                    Err(e) => error!("Error computing diagnostics: {}", e),
+                }
+                // Not retried by the next run, which would likely fail again.
+                let mut pending = lock(&self.pending);
+                if !token.is_cancelled() {
+                    pending.uris.clear();
+                }
+                return;
+            }
+        };
+
+        // A newer run now owns the pending files and will publish fresher
+        // diagnostics for them.
+        {
+            let mut pending = lock(&self.pending);
+            if token.is_cancelled() {
+                return;
+            }
+            pending.uris.clear();
+        }
+        for (url, diagnostics) in diagnostics {
+            client.publish_diagnostics(url, diagnostics, None).await;
+        }
+    }
+}
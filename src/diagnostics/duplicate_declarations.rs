@@ -0,0 +1,342 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range, Url,
+};
+
+use crate::{
+    index::{
+        include_graph::{IncludeGraph, IncludeStatement},
+        symbols::FileSymbols,
+    },
+    utils::get_basename_from_uri,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DeclarationKind {
+    Variable,
+    Procedure,
+}
+
+#[derive(Debug, Clone)]
+struct Declaration {
+    kind: DeclarationKind,
+    name: String,
+    type_description: Option<String>,
+    uri: String,
+    range: Range,
+    /// The include statement in the analyzed file that pulls this
+    /// declaration in, or `None` when it is declared in the file itself.
+    via: Option<IncludeStatement>,
+}
+
+/// Reports variables and procedures declared more than once across `driver`
+/// and everything it includes. The first declaration in compile order is
+/// the original; every later one is a duplicate.
+///
+/// Only diagnostics that belong in `uri` are returned. When `uri` is the
+/// driver, duplicates coming from include files are reported on the include
+/// statement that brings them in. When `uri` is one of the driver's include
+/// files, the duplicates declared in it are reported where they are declared.
+pub fn get_duplicate_declaration_diagnostics(
+    graph: &IncludeGraph,
+    symbols: &HashMap<String, Arc<FileSymbols>>,
+    driver: &str,
+    uri: &str,
+) -> Vec<Diagnostic> {
+    let mut declarations: Vec<Declaration> = Vec::new();
+    let mut visited: HashSet<String> = HashSet::from([driver.to_string()]);
+    collect_declarations(
+        graph,
+        symbols,
+        driver,
+        None,
+        &mut visited,
+        &mut declarations,
+    );
+
+    let mut groups: Vec<(DeclarationKind, String, Vec<&Declaration>)> = Vec::new();
+    for declaration in &declarations {
+        let key = declaration.name.to_uppercase();
+        match groups
+            .iter_mut()
+            .find(|(kind, name, _)| *kind == declaration.kind && *name == key)
+        {
+            Some((_, _, group)) => group.push(declaration),
+            None => groups.push((declaration.kind, key, vec![declaration])),
+        }
+    }
+
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut reported: HashSet<(String, u32, u32)> = HashSet::new();
+    for (kind, name, group) in groups.iter().filter(|(_, _, group)| group.len() > 1) {
+        let original = group[0];
+        for duplicate in group.iter().skip(1) {
+            let range = match &duplicate.via {
+                _ if driver != uri && duplicate.uri != uri => continue,
+                _ if driver != uri => duplicate.range,
+                Some(include) => include.range,
+                None => duplicate.range,
+            };
+            if !reported.insert((name.clone(), range.start.line, range.start.character)) {
+                continue;
+            }
+
+            let reported_on_include = driver == uri && duplicate.via.is_some();
+            let mut message = get_message(*kind, original, duplicate, reported_on_include);
+            if driver != uri {
+                message = format!(
+                    "{} (included by {})",
+                    message,
+                    get_basename_from_uri(driver)
+                );
+            }
+            // A redeclaration reported on an include statement links to its
+            // own site as well.
+            let related_information = group
+                .iter()
+                .filter(|other| reported_on_include || !std::ptr::eq(**other, *duplicate))
+                .filter_map(|other| related_information(other))
+                .collect::<Vec<DiagnosticRelatedInformation>>();
+
+            diagnostics.push(Diagnostic {
+                range,
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("pols".to_string()),
+                message,
+                related_information: Some(related_information),
+                ..Diagnostic::default()
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Walks `uri` in compile order, expanding each include where it appears.
+fn collect_declarations(
+    graph: &IncludeGraph,
+    symbols: &HashMap<String, Arc<FileSymbols>>,
+    uri: &str,
+    via: Option<&IncludeStatement>,
+    visited: &mut HashSet<String>,
+    declarations: &mut Vec<Declaration>,
+) {
+    enum Item<'a> {
+        Declaration(Declaration),
+        Include(&'a IncludeStatement),
+    }
+
+    let mut items: Vec<(Range, Item)> = Vec::new();
+    if let Some(file_symbols) = symbols.get(uri) {
+        for variable in &file_symbols.variables {
+            items.push((
+                variable.range,
+                Item::Declaration(Declaration {
+                    kind: DeclarationKind::Variable,
+                    name: variable.name.clone(),
+                    type_description: Some(variable.type_description()),
+                    uri: uri.to_string(),
+                    range: variable.range,
+                    via: via.cloned(),
+                }),
+            ));
+        }
+        for procedure in &file_symbols.procedures {
+            items.push((
+                procedure.range,
+                Item::Declaration(Declaration {
+                    kind: DeclarationKind::Procedure,
+                    name: procedure.name.clone(),
+                    type_description: None,
+                    uri: uri.to_string(),
+                    range: procedure.range,
+                    via: via.cloned(),
+                }),
+            ));
+        }
+    }
+    for include in graph.include_statements(uri) {
+        items.push((include.range, Item::Include(include)));
+    }
+    items.sort_by_key(|(range, _)| (range.start.line, range.start.character));
+
+    for (_, item) in items {
+        match item {
+            Item::Declaration(declaration) => declarations.push(declaration),
+            Item::Include(include) => {
+                let target = match graph.resolve(&include.name) {
+                    Some(target) => target.clone(),
+                    None => continue,
+                };
+                if visited.insert(target.clone()) {
+                    let via = via.or(Some(include));
+                    collect_declarations(graph, symbols, &target, via, visited, declarations);
+                }
+            }
+        }
+    }
+}
+
+fn get_message(
+    kind: DeclarationKind,
+    original: &Declaration,
+    duplicate: &Declaration,
+    on_include: bool,
+) -> String {
+    let name = &duplicate.name;
+    let original_file = get_basename_from_uri(&original.uri);
+    let location = match on_include {
+        true => format!(" by {}", get_basename_from_uri(&duplicate.uri)),
+        false => String::new(),
+    };
+    match kind {
+        DeclarationKind::Procedure => format!(
+            "Duplicate procedure {}{}, already defined in {}",
+            name, location, original_file
+        ),
+        DeclarationKind::Variable if original.type_description != duplicate.type_description => {
+            format!(
+                "Conflicting declaration of {}{} as {}, already declared in {} as {}",
+                name,
+                location,
+                duplicate.type_description.clone().unwrap_or_default(),
+                original_file,
+                original.type_description.clone().unwrap_or_default()
+            )
+        }
+        DeclarationKind::Variable => format!(
+            "Duplicate declaration of {}{}, already declared in {}",
+            name, location, original_file
+        ),
+    }
+}
+
+fn related_information(declaration: &Declaration) -> Option<DiagnosticRelatedInformation> {
+    let uri = Url::parse(&declaration.uri).ok()?;
+    let message = match (&declaration.kind, &declaration.type_description) {
+        (DeclarationKind::Variable, Some(type_description)) => {
+            format!("{} declared here as {}", declaration.name, type_description)
+        }
+        (DeclarationKind::Variable, None) => format!("{} declared here", declaration.name),
+        (DeclarationKind::Procedure, _) => format!("{} defined here", declaration.name),
+    };
+    Some(DiagnosticRelatedInformation {
+        location: Location {
+            uri,
+            range: declaration.range,
+        },
+        message,
+    })
+}
+
+#[test]
+fn test_duplicates_across_includes() {
+    use crate::index::symbols::{ProcedureSymbol, VariableSymbol};
+    use tower_lsp::lsp_types::Position;
+
+    let range = |line: u32| Range {
+        start: Position { line, character: 0 },
+        end: Position { line, character: 4 },
+    };
+    let variable = |name: &str, data_type, line| VariableSymbol {
+        name: name.to_string(),
+        data_type: Some(data_type),
+        size: None,
+        is_array: false,
+        range: range(line),
+        declaration_range: range(line),
+    };
+    let include = |name: &str, line| IncludeStatement {
+        name: name.to_string(),
+        range: range(line),
+    };
+
+    use crate::database::types::DataType;
+    let mut graph = IncludeGraph::new();
+    graph.update_file(
+        "file:///specs/DRIVER",
+        vec![include("A.DEF", 1), include("B.PRO", 3)],
+    );
+    graph.update_file("file:///specs/A.DEF", vec![]);
+    graph.update_file("file:///specs/B.PRO", vec![]);
+
+    let mut symbols: HashMap<String, Arc<FileSymbols>> = HashMap::new();
+    symbols.insert(
+        "file:///specs/DRIVER".to_string(),
+        Arc::new(FileSymbols {
+            variables: vec![variable("TRUE", DataType::Character, 0)],
+            procedures: vec![ProcedureSymbol {
+                name: "PRINTHEADER".to_string(),
+                range: range(4),
+                definition_range: range(4),
+            }],
+            ..FileSymbols::default()
+        }),
+    );
+    symbols.insert(
+        "file:///specs/A.DEF".to_string(),
+        Arc::new(FileSymbols {
+            variables: vec![variable("TRUE", DataType::Number, 0)],
+            ..FileSymbols::default()
+        }),
+    );
+    symbols.insert(
+        "file:///specs/B.PRO".to_string(),
+        Arc::new(FileSymbols {
+            procedures: vec![ProcedureSymbol {
+                name: "PrintHeader".to_string(),
+                range: range(0),
+                definition_range: range(0),
+            }],
+            ..FileSymbols::default()
+        }),
+    );
+
+    let diagnostics = get_duplicate_declaration_diagnostics(
+        &graph,
+        &symbols,
+        "file:///specs/DRIVER",
+        "file:///specs/DRIVER",
+    );
+    let messages: Vec<(u32, &str)> = diagnostics
+        .iter()
+        .map(|d| (d.range.start.line, d.message.as_str()))
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                1,
+                "Conflicting declaration of TRUE by A.DEF as NUMBER, already declared in DRIVER as CHARACTER"
+            ),
+            (
+                4,
+                "Duplicate procedure PRINTHEADER, already defined in B.PRO"
+            ),
+        ]
+    );
+    assert_eq!(
+        diagnostics[0].related_information.as_ref().unwrap().len(),
+        2
+    );
+    assert_eq!(
+        diagnostics[1].related_information.as_ref().unwrap().len(),
+        1
+    );
+
+    let diagnostics = get_duplicate_declaration_diagnostics(
+        &graph,
+        &symbols,
+        "file:///specs/DRIVER",
+        "file:///specs/A.DEF",
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 0);
+    assert_eq!(
+        diagnostics[0].message,
+        "Conflicting declaration of TRUE as NUMBER, already declared in DRIVER as CHARACTER (included by DRIVER)"
+    );
+}
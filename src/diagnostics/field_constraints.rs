@@ -0,0 +1,59 @@
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use tree_sitter::{Node, Tree};
+
+use crate::utils::node_range;
+
+use super::field_values::{check_constant, compared_field};
+
+/// Reports constants compared with a field that cannot hold them, such as a
+/// string longer than the field or a code above its maximum. Such a
+/// comparison always comes out the same way.
+pub fn get_field_constraint_diagnostics(tree: &Tree, text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    collect_diagnostics(tree.root_node(), text, &mut diagnostics);
+    diagnostics
+}
+
+fn collect_diagnostics(node: Node, text: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if matches!(node.kind(), "string_literal" | "number") {
+        let message =
+            compared_field(node, text).and_then(|(_, field)| check_constant(field, node, text));
+        if let Some(message) = message {
+            diagnostics.push(Diagnostic {
+                range: node_range(&node, text),
+                severity: Some(DiagnosticSeverity::WARNING),
+                source: Some("pols".to_string()),
+                message: format!("{} The comparison always has the same result.", message),
+                ..Diagnostic::default()
+            });
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_diagnostics(child, text, diagnostics);
+    }
+}
+
+#[test]
+fn test_field_constraint_diagnostics() {
+    let text = "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n IF ACCOUNT:BRANCH=12000 THEN\n  PRINT 1\n \
+                IF 9999<>ACCOUNT:BRANCH THEN\n  PRINT 2\n IF ACCOUNT:NUMBER=\"12345678901\" THEN\n  \
+                PRINT 3\n IF ACCOUNT:NUMBER=\"1234567890\" THEN\n  PRINT 4\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let diagnostics = get_field_constraint_diagnostics(&tree, text);
+
+    assert_eq!(
+        crate::test_utils::diagnostic_lines(&diagnostics),
+        vec![
+            (
+                2,
+                "12000 is not a valid code. BRANCH is a code from 0 to 9999. The comparison always has the same result."
+            ),
+            (
+                6,
+                "\"12345678901\" is 11 characters long, but NUMBER holds 10. The comparison always has the same result."
+            ),
+        ]
+    );
+}
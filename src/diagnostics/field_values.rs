@@ -0,0 +1,201 @@
+use tree_sitter::Node;
+
+use crate::{
+    database::{
+        functions::BUILTIN_FUNCTIONS,
+        record_types::record_fields,
+        types::{BuiltinFunction, DataType, DatabaseField, ReturnType, Syntax},
+    },
+    index::{analysis::Scope, record_scope::field_record},
+};
+
+/// Operators that compare their operands.
+const COMPARISONS: &[&str] = &["=", "<>", "<", "<=", ">", ">="];
+
+/// The type of a literal node, or None for anything else.
+pub fn literal_type(node: Node) -> Option<DataType> {
+    match node.kind() {
+        "string_literal" => Some(DataType::Character),
+        "number" => Some(DataType::Number),
+        "money" => Some(DataType::Money),
+        "date" => Some(DataType::Date),
+        "rate" => Some(DataType::Rate),
+        _ => None,
+    }
+}
+
+/// The type of a literal, variable, field or builtin call, or None when it is
+/// not known.
+pub fn value_type(node: Node, text: &str, scope: &Scope) -> Option<DataType> {
+    match node.kind() {
+        "identifier" => {
+            let name = node.utf8_text(text.as_bytes()).ok()?;
+            scope.variable(name)?.data_type
+        }
+        "database_field" => Some(database_field(node, text)?.data_type),
+        "poweron_function" => {
+            let (function, call) = builtin_call(node, text)?;
+            match function.returns {
+                ReturnType::Value(data_type) => Some(data_type),
+                ReturnType::Argument => value_type(call.named_child(0)?, text, scope),
+                ReturnType::Nothing | ReturnType::Boolean => None,
+            }
+        }
+        "keyword" => {
+            let (function, arguments) = builtin_arguments(node, text)?;
+            match function.returns {
+                ReturnType::Value(data_type) => Some(data_type),
+                ReturnType::Argument => value_type(*arguments.first()?, text, scope),
+                ReturnType::Nothing | ReturnType::Boolean => None,
+            }
+        }
+        "parenthesized_expression" => value_type(node.named_child(0)?, text, scope),
+        _ => literal_type(node),
+    }
+}
+
+/// The catalog entry of the builtin a `poweron_function` node calls, and the
+/// node under it that holds the arguments.
+pub fn builtin_call<'a>(
+    node: Node<'a>,
+    text: &str,
+) -> Option<(&'static BuiltinFunction, Node<'a>)> {
+    let call = node.named_child(0)?;
+    Some((builtin_named(call, text)?, call))
+}
+
+/// The catalog entry of the builtin whose name `node` starts with.
+pub fn builtin_named(node: Node, text: &str) -> Option<&'static BuiltinFunction> {
+    let name: String = text[node.start_byte()..node.end_byte()]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    BUILTIN_FUNCTIONS.get(name.to_uppercase().as_str())
+}
+
+/// The catalog entry of the builtin `node` calls and its arguments, when they
+/// line up with the catalog's parameters: calls, builtins the grammar only
+/// knows as a keyword followed by parentheses, such as `AFTERLAST`, and the
+/// warning statements of FMPERFORM blocks.
+pub fn builtin_arguments<'a>(
+    node: Node<'a>,
+    text: &str,
+) -> Option<(&'static BuiltinFunction, Vec<Node<'a>>)> {
+    match node.kind() {
+        "poweron_function" => {
+            let (function, call) = builtin_call(node, text)?;
+            (function.syntax == Syntax::Call).then(|| (function, arguments_of(call)))
+        }
+        "keyword" => {
+            let function = builtin_named(node, text)?;
+            let arguments = following_node(node)
+                .filter(|next| next.kind() == "parenthesized_expression")
+                .filter(|next| next.start_byte() == node.end_byte())?;
+            (function.syntax == Syntax::Call).then(|| (function, arguments_of(arguments)))
+        }
+        "setwarn" | "clearwarn" => Some((builtin_named(node, text)?, arguments_of(node))),
+        _ => None,
+    }
+}
+
+/// The named children of `node`, with the nodes of any parse errors among
+/// them in their place, as a comma the grammar did not expect is an error.
+fn arguments_of(node: Node) -> Vec<Node> {
+    let mut arguments = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() == "ERROR" {
+            let mut cursor = child.walk();
+            arguments.extend(child.named_children(&mut cursor));
+        } else if !child.is_extra() {
+            arguments.push(child);
+        }
+    }
+    arguments
+}
+
+/// The node that follows `node`, which may be a sibling of an ancestor.
+fn following_node(node: Node) -> Option<Node> {
+    let mut current = node;
+    loop {
+        if let Some(next) = current.next_sibling() {
+            return Some(next);
+        }
+        current = current.parent()?;
+    }
+}
+
+/// The catalog entry of the field a `database_field` node reads.
+pub fn database_field(node: Node, text: &str) -> Option<&'static DatabaseField> {
+    let record = field_record(node, text)?;
+    let mut cursor = node.walk();
+    let field_name = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "field_name")?;
+    let name = field_name
+        .named_child(0)
+        .unwrap_or(field_name)
+        .utf8_text(text.as_bytes())
+        .ok()?;
+    record_fields(&record)?.get(name.to_lowercase().as_str())
+}
+
+/// What is wrong with the constant `node` as a value of `field`: a string
+/// longer than the field or a code above its maximum. None for anything
+/// else, including values that are not constants.
+pub fn check_constant(field: &DatabaseField, node: Node, text: &str) -> Option<String> {
+    let value = node.utf8_text(text.as_bytes()).ok()?;
+    let length = field.length?;
+    match (field.data_type, node.kind()) {
+        (DataType::Character, "string_literal") => {
+            let characters = value.trim_matches('"').chars().count();
+            (characters > length as usize).then(|| {
+                format!(
+                    "{} is {} characters long, but {} holds {}.",
+                    value,
+                    characters,
+                    field.mnemonic.to_uppercase(),
+                    length
+                )
+            })
+        }
+        (DataType::Code, "number") => {
+            let code: i64 = value.parse().ok()?;
+            (code < 0 || code > length as i64).then(|| {
+                format!(
+                    "{} is not a valid code. {} is a code from 0 to {}.",
+                    value,
+                    field.mnemonic.to_uppercase(),
+                    length
+                )
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The field a literal is compared with, as the record and its catalog
+/// entry, when the other side of the comparison reads one.
+pub fn compared_field(node: Node, text: &str) -> Option<(String, &'static DatabaseField)> {
+    let comparison = node.parent()?;
+    let operator = comparison.child_by_field_name("operator")?;
+    if comparison.kind() != "binary_expression" || !COMPARISONS.contains(&operator.kind()) {
+        return None;
+    }
+    let left = comparison.child_by_field_name("left")?;
+    let right = comparison.child_by_field_name("right")?;
+    let other = if left.id() == node.id() { right } else { left };
+    if other.kind() != "database_field" {
+        return None;
+    }
+    Some((field_record(other, text)?, database_field(other, text)?))
+}
+
+/// The values `field` accepts, such as `a code from 0 to 9999`.
+pub fn describe_field(field: &DatabaseField) -> String {
+    match (field.data_type, field.length) {
+        (DataType::Character, Some(length)) => format!("up to {} characters", length),
+        (DataType::Code, Some(length)) => format!("a code from 0 to {}", length),
+        (data_type, _) => format!("a {} value", data_type.as_str().to_lowercase()),
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use tree_sitter::{Node, Tree};
+
+use crate::{
+    database::record_types::{normalize_record_type, parent_record, record_fields},
+    index::{analysis::Scope, record_scope::records_at},
+    utils::node_range,
+};
+
+use super::field_values::{check_constant, value_type};
+
+/// Reports mistakes in `FMPERFORM` blocks: a record that cannot be reached
+/// for the operation, SET statements naming fields the record lacks, values
+/// of the wrong type or size, writes to system-entered fields, and error
+/// variables that nothing reads after the block. `procedures` tells what is
+/// current in each procedure, as for `records_at`.
+pub fn get_file_maintenance_diagnostics(
+    tree: &Tree,
+    text: &str,
+    procedures: &HashMap<String, Vec<String>>,
+    scope: &Scope,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for node in fmperform_nodes(tree.root_node()) {
+        let fm = match FileMaintenance::new(node, text) {
+            Some(fm) => fm,
+            None => continue,
+        };
+        if let Some(records) = records_at(tree, text, node.start_byte(), procedures) {
+            diagnostics.extend(fm.check_record(&records));
+        }
+        if fm.operation != "DELETE" {
+            diagnostics.extend(fm.check_sets(scope));
+        }
+        diagnostics.extend(fm.check_error_variable());
+    }
+    diagnostics
+}
+
+/// An `FMPERFORM` block.
+struct FileMaintenance<'a> {
+    node: Node<'a>,
+    text: &'a str,
+    /// `CREATE`, `REVISE` or `DELETE`.
+    operation: String,
+    record: String,
+    record_node: Node<'a>,
+    /// Whether the record path names which record to work on, with `LOC` or
+    /// an identifier, instead of using the current one.
+    located: bool,
+}
+
+impl<'a> FileMaintenance<'a> {
+    fn new(node: Node<'a>, text: &'a str) -> Option<Self> {
+        let source = text.as_bytes();
+        let operation = child_of_kind(node, "fmtype")?
+            .utf8_text(source)
+            .ok()?
+            .to_uppercase();
+        let path = child_of_kind(node, "recordPath")?;
+        let mut cursor = path.walk();
+        let parts: Vec<Node> = path.children(&mut cursor).collect();
+        let record_node = parts
+            .iter()
+            .rev()
+            .find(|part| part.kind() == "record_type")
+            .copied()?;
+        let record = normalize_record_type(record_node.utf8_text(source).ok()?);
+        let located = parts.iter().any(|part| part.kind() != "record_type");
+        Some(Self {
+            node,
+            text,
+            operation,
+            record,
+            record_node,
+            located,
+        })
+    }
+
+    /// A record is created under the current record it belongs to. One that
+    /// is revised or deleted is the current one, unless the path locates it,
+    /// in which case it is looked up under the current parent.
+    fn check_record(&self, records: &[String]) -> Option<Diagnostic> {
+        let parent = parent_record(&self.record);
+        let needed = match (self.operation.as_str(), self.located) {
+            ("CREATE", _) | (_, true) => parent?,
+            _ => self.record.clone(),
+        };
+        if records.contains(&needed) {
+            return None;
+        }
+        let message = match self.operation.as_str() {
+            "CREATE" => format!(
+                "FMPERFORM CREATE {} needs a current {} to create it under.",
+                self.record, needed
+            ),
+            operation if self.located => format!(
+                "FMPERFORM {} {} needs a current {} to find it under.",
+                operation, self.record, needed
+            ),
+            operation => format!(
+                "FMPERFORM {} {} needs a current {}, or LOC to name the record.",
+                operation, self.record, needed
+            ),
+        };
+        Some(diagnostic(
+            self.record_node,
+            self.text,
+            DiagnosticSeverity::ERROR,
+            message,
+        ))
+    }
+
+    fn check_sets(&self, scope: &Scope) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let fields = match record_fields(&self.record) {
+            Some(fields) => fields,
+            None => return diagnostics,
+        };
+        let mut cursor = self.node.walk();
+        for set in self
+            .node
+            .children(&mut cursor)
+            .filter(|child| child.kind() == "setexp")
+        {
+            let (name_node, value) = match (set.named_child(0), set.named_child(1)) {
+                (Some(name_node), Some(value)) => (name_node, value),
+                _ => continue,
+            };
+            let name_node = name_node.named_child(0).unwrap_or(name_node);
+            let name = match name_node.utf8_text(self.text.as_bytes()) {
+                Ok(name) => name.to_uppercase(),
+                Err(_) => continue,
+            };
+            let field = match fields.get(name.to_lowercase().as_str()) {
+                Some(field) => field,
+                None => {
+                    diagnostics.push(diagnostic(
+                        name_node,
+                        self.text,
+                        DiagnosticSeverity::ERROR,
+                        format!("{} has no field {}.", self.record, name),
+                    ));
+                    continue;
+                }
+            };
+            if field.is_system_entered() {
+                diagnostics.push(diagnostic(
+                    name_node,
+                    self.text,
+                    DiagnosticSeverity::WARNING,
+                    format!(
+                        "{}:{} is system-entered. File maintenance cannot set it.",
+                        self.record, name
+                    ),
+                ));
+            }
+            match value_type(value, self.text, scope) {
+                Some(data_type) if !data_type.is_compatible_with(field.data_type) => diagnostics
+                    .push(diagnostic(
+                        value,
+                        self.text,
+                        DiagnosticSeverity::ERROR,
+                        format!(
+                            "{}:{} is {}, but the value is {}.",
+                            self.record,
+                            name,
+                            field.type_description(),
+                            data_type.as_str()
+                        ),
+                    )),
+                _ => {
+                    if let Some(message) = check_constant(field, value, self.text) {
+                        diagnostics.push(diagnostic(
+                            value,
+                            self.text,
+                            DiagnosticSeverity::ERROR,
+                            message,
+                        ));
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+
+    /// The last option names the variable the host stores an error in. The
+    /// statements after the block should read it before anything else
+    /// overwrites it. A block at the end of a procedure may be checked by
+    /// the caller, so it is left alone.
+    fn check_error_variable(&self) -> Option<Diagnostic> {
+        let variable = error_variable(self.node)?;
+        let name = variable.utf8_text(self.text.as_bytes()).ok()?;
+
+        let mut statement = statement_of(self.node);
+        loop {
+            let mut next = statement.next_sibling();
+            while let Some(sibling) = next {
+                if reads_variable(sibling, name, self.text) {
+                    return None;
+                }
+                if fmperform_nodes(sibling)
+                    .iter()
+                    .filter_map(|fm| error_variable(*fm))
+                    .any(|other| other.utf8_text(self.text.as_bytes()) == Ok(name))
+                {
+                    break;
+                }
+                next = sibling.next_sibling();
+            }
+            if next.is_some() {
+                break;
+            }
+            statement = match statement.parent() {
+                Some(parent) if parent.kind() == "procedure_definition" => return None,
+                Some(parent) if !is_division(parent) => parent,
+                _ => break,
+            };
+        }
+        Some(diagnostic(
+            variable,
+            self.text,
+            DiagnosticSeverity::WARNING,
+            format!(
+                "{} is not checked after this FMPERFORM, so a failed file maintenance goes unnoticed.",
+                name.to_uppercase()
+            ),
+        ))
+    }
+}
+
+fn is_division(node: Node) -> bool {
+    node.kind() == "source_file" || node.kind().ends_with("_division")
+}
+
+fn fmperform_nodes(node: Node) -> Vec<Node> {
+    if node.kind() == "fmperform" {
+        return vec![node];
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children.into_iter().flat_map(fmperform_nodes).collect()
+}
+
+/// The variable the last option of an `FMPERFORM` names.
+fn error_variable(node: Node) -> Option<Node> {
+    let options = child_of_kind(node, "fmperformoptions")?;
+    let variable = options.named_child(options.named_child_count().checked_sub(1)?)?;
+    (variable.kind() == "identifier").then_some(variable)
+}
+
+/// The `FMPERFORM` statement itself, which the grammar wraps as a function.
+fn statement_of(node: Node) -> Node {
+    match node.parent() {
+        Some(parent) if parent.kind() == "poweron_function" => parent,
+        _ => node,
+    }
+}
+
+/// Whether `node` reads `name`, apart from naming it as the error variable
+/// of another block.
+fn reads_variable(node: Node, name: &str, text: &str) -> bool {
+    if node.kind() == "fmperformoptions" {
+        return false;
+    }
+    if node.kind() == "identifier" {
+        return node
+            .utf8_text(text.as_bytes())
+            .is_ok_and(|identifier| identifier.eq_ignore_ascii_case(name));
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children
+        .into_iter()
+        .any(|child| reads_variable(child, name, text))
+}
+
+fn child_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let child = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == kind);
+    child
+}
+
+fn diagnostic(node: Node, text: &str, severity: DiagnosticSeverity, message: String) -> Diagnostic {
+    Diagnostic {
+        range: node_range(&node, text),
+        severity: Some(severity),
+        source: Some("pols".to_string()),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+#[test]
+fn test_file_maintenance_diagnostics() {
+    use crate::test_utils::{diagnostic_lines, scope_of, snapshot_with};
+
+    let uri = "file:///specs/DRIVER";
+    let text =
+        "TARGET=ACCOUNT\nDEFINE\n FMERROR=CHARACTER\n OPENED=DATE\nEND\nPRINT TITLE=\"X\"\n \
+                FMPERFORM REVISE ACCOUNT (0,0,FMERROR)\n  DO\n   SET BRANCH TO 12000\n   \
+                SET NUMBER TO \"0000000001\"\n   SET BRANCH TO OPENED\n   SET COLOR TO 1\n   \
+                SET CLOSEDATE TO OPENED\n  END\n IF FMERROR<>\"\" THEN\n  PRINT FMERROR\n \
+                FMPERFORM CREATE SHARE TRANSFER LOC AVAILABLE (0,0,FMERROR)\n  DO\n  END\nEND\n";
+    let snapshot = snapshot_with(&[(uri, text)]);
+    let scope = scope_of(&snapshot, uri);
+    let tree = crate::parser::parse(text).unwrap();
+    let diagnostics = get_file_maintenance_diagnostics(&tree, text, &HashMap::new(), &scope);
+
+    assert_eq!(
+        diagnostic_lines(&diagnostics),
+        vec![
+            (8, "12000 is not a valid code. BRANCH is a code from 0 to 9999."),
+            (9, "ACCOUNT:NUMBER is system-entered. File maintenance cannot set it."),
+            (10, "ACCOUNT:BRANCH is Code to 9999, but the value is DATE."),
+            (11, "ACCOUNT has no field COLOR."),
+            (
+                16,
+                "FMPERFORM CREATE SHARE TRANSFER needs a current SHARE to create it under."
+            ),
+            (
+                16,
+                "FMERROR is not checked after this FMPERFORM, so a failed file maintenance goes unnoticed."
+            ),
+        ]
+    );
+}
@@ -0,0 +1,84 @@
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use tree_sitter::{Node, Tree};
+
+use crate::{index::analysis::Scope, utils::node_range};
+
+use super::field_values::{builtin_arguments, value_type};
+
+/// Reports arguments of builtin calls whose type is not the one the
+/// function catalog gives for their parameter. Arguments of unknown type
+/// and parameters that take any type are not checked.
+pub fn get_function_call_diagnostics(tree: &Tree, text: &str, scope: &Scope) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    collect_diagnostics(tree.root_node(), text, scope, &mut diagnostics);
+    diagnostics
+}
+
+fn collect_diagnostics(node: Node, text: &str, scope: &Scope, diagnostics: &mut Vec<Diagnostic>) {
+    diagnostics.extend(check_arguments(node, text, scope));
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_diagnostics(child, text, scope, diagnostics);
+    }
+}
+
+fn check_arguments(node: Node, text: &str, scope: &Scope) -> Vec<Diagnostic> {
+    let (function, arguments) = match builtin_arguments(node, text) {
+        Some(call) => call,
+        None => return Vec::new(),
+    };
+    arguments
+        .into_iter()
+        .zip(&function.parameters)
+        .filter_map(|(argument, parameter)| {
+            let expected = parameter.data_type?;
+            let found = value_type(argument, text, scope)?;
+            if found.is_compatible_with(expected) {
+                return None;
+            }
+            Some(Diagnostic {
+                range: node_range(&argument, text),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("pols".to_string()),
+                message: format!(
+                    "{} of {} is {}, but the value is {}.",
+                    parameter.name,
+                    function.name,
+                    expected.as_str(),
+                    found.as_str()
+                ),
+                ..Diagnostic::default()
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_function_call_diagnostics() {
+    use crate::test_utils::{diagnostic_lines, scope_of, snapshot_with};
+
+    let uri = "file:///specs/DRIVER";
+    let text = "TARGET=ACCOUNT\nDEFINE\n OPENED=DATE\n COUNT=NUMBER\n FMERROR=CHARACTER\nEND\n\
+                PRINT TITLE=\"X\"\n COUNT=LENGTH(OPENED)\n COUNT=LENGTH(ACCOUNT:BRANCH)+DAY(OPENED)\n \
+                COUNT=MOD(ABS(OPENED),2)\n PRINT SEGMENT(\"ABC\",1,LENGTH(\"AB\"))\n \
+                FMERROR=AFTERLAST(OPENED,\".\")\n FMPERFORM REVISE ACCOUNT (0,0,FMERROR)\n  DO\n   \
+                SETWARNING OPENED OPENED\n   CLEARWARNING 5\n  END\nEND\n";
+    let snapshot = snapshot_with(&[(uri, text)]);
+    let scope = scope_of(&snapshot, uri);
+    let tree = crate::parser::parse(text).unwrap();
+    let diagnostics = get_function_call_diagnostics(&tree, text, &scope);
+
+    assert_eq!(
+        diagnostic_lines(&diagnostics),
+        vec![
+            (7, "Text of LENGTH is CHARACTER, but the value is DATE."),
+            (8, "Text of LENGTH is CHARACTER, but the value is CODE."),
+            (9, "Dividend of MOD is NUMBER, but the value is DATE."),
+            (11, "Text of AFTERLAST is CHARACTER, but the value is DATE."),
+            (
+                14,
+                "WarningCode of SETWARNING is NUMBER, but the value is DATE."
+            ),
+        ]
+    );
+}
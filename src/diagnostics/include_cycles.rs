@@ -0,0 +1,25 @@
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::{index::include_graph::IncludeGraph, utils::get_basename_from_uri};
+
+/// Reports every include statement in `uri` that leads back to `uri`.
+pub fn get_include_cycle_diagnostics(graph: &IncludeGraph, uri: &str) -> Vec<Diagnostic> {
+    graph
+        .cycles_from(uri)
+        .into_iter()
+        .map(|(include, cycle)| {
+            let cycle = cycle
+                .iter()
+                .map(|file| get_basename_from_uri(file))
+                .collect::<Vec<String>>()
+                .join(" -> ");
+            Diagnostic {
+                range: include.range,
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("pols".to_string()),
+                message: format!("Include cycle: {}", cycle),
+                ..Diagnostic::default()
+            }
+        })
+        .collect()
+}
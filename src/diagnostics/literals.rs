@@ -0,0 +1,338 @@
+use std::fmt;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use tree_sitter::{Node, Point, Tree};
+
+use crate::{text::point_to_position, utils::node_range};
+
+/// The smallest and largest NUMBER, which holds a signed 32-bit whole
+/// number.
+const NUMBER_MIN: i64 = -2_147_483_648;
+const NUMBER_MAX: i64 = 2_147_483_647;
+
+/// The largest rate, with three decimal places.
+const RATE_MAX: f64 = 100.0;
+
+/// Two-digit years below this are read as 20YY and the rest as 19YY.
+const CENTURY_PIVOT: u32 = 50;
+
+/// A calendar date written as a literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Parses a date literal such as `'12/31/23'`. `Ok(None)` is a null date
+    /// such as `'--/--/--'`.
+    pub fn parse(literal: &str) -> Result<Option<Date>, String> {
+        let body = literal.trim_matches('\'');
+        if body.chars().all(|c| c == '-' || c == '/') {
+            return Ok(None);
+        }
+        let parts: Vec<u32> = body
+            .split('/')
+            .map(|part| part.parse::<u32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("{} is not a date. Write dates as 'MM/DD/YYYY'.", literal))?;
+        let (month, day, year) = match parts[..] {
+            [month, day, year] => (month, day, year),
+            _ => {
+                return Err(format!(
+                    "{} is not a date. Write dates as 'MM/DD/YYYY'.",
+                    literal
+                ))
+            }
+        };
+        let year = match body.rsplit('/').next().map(str::len) {
+            Some(2) if year < CENTURY_PIVOT => 2000 + year,
+            Some(2) => 1900 + year,
+            _ => year,
+        };
+        if !(1..=12).contains(&month) {
+            return Err(format!("{} has no month {}.", literal, month));
+        }
+        let days = days_in_month(year, month);
+        if day == 0 || day > days {
+            return Err(format!(
+                "{} is not a date. {}/{} has {} days.",
+                literal, month, year, days
+            ));
+        }
+        Ok(Some(Date { year, month, day }))
+    }
+
+    pub fn weekday(&self) -> &'static str {
+        const WEEKDAYS: [&str; 7] = [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ];
+        // Sakamoto's method.
+        const OFFSETS: [i64; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = self.year as i64 - i64::from(self.month < 3);
+        let day = year + year.div_euclid(4) - year.div_euclid(100)
+            + year.div_euclid(400)
+            + OFFSETS[self.month as usize - 1]
+            + self.day as i64;
+        WEEKDAYS[day.rem_euclid(7) as usize]
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}/{:02}/{:04}", self.month, self.day, self.year)
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Reports date, money, rate and number literals the host would reject.
+pub fn get_literal_diagnostics(tree: &Tree, text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    collect_diagnostics(tree.root_node(), text, &mut diagnostics);
+    diagnostics
+}
+
+fn collect_diagnostics(node: Node, text: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let literal = match node.utf8_text(text.as_bytes()) {
+        Ok(literal) if node.child_count() == 0 => literal,
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_diagnostics(child, text, diagnostics);
+            }
+            return;
+        }
+    };
+    let problem = match node.kind() {
+        "date" => Date::parse(literal)
+            .err()
+            .map(|message| (node_range(&node, text), message)),
+        "rate" => check_rate(literal).map(|message| (node_range(&node, text), message)),
+        "number" => check_number(literal).map(|message| (node_range(&node, text), message)),
+        // Money the grammar does not accept lexes as an identifier that
+        // starts with `$`, followed by the rest of the amount.
+        "identifier" if is_money_like(literal) => check_money_at(node, text),
+        _ => None,
+    };
+    if let Some((range, message)) = problem {
+        diagnostics.push(Diagnostic {
+            range,
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some("pols".to_string()),
+            message,
+            ..Diagnostic::default()
+        });
+    }
+}
+
+fn check_rate(literal: &str) -> Option<String> {
+    let rate: f64 = literal.trim_end_matches('%').parse().ok()?;
+    (rate > RATE_MAX).then(|| {
+        format!(
+            "{} is out of range. Rates run from 0.000% to {:.3}%.",
+            literal, RATE_MAX
+        )
+    })
+}
+
+fn check_number(literal: &str) -> Option<String> {
+    let digits = literal.trim_start_matches(['-', '+']);
+    if digits.contains(['x', 'X', 'b', 'B', 'o', 'O', 'e', 'E', '_']) {
+        return Some(format!(
+            "{} is not a PowerOn number. Write numbers in decimal digits.",
+            literal
+        ));
+    }
+    if digits.contains('.') {
+        return None;
+    }
+    // Too many digits for an i64 is out of range in the direction of the sign.
+    let number = match literal.parse::<i64>() {
+        Ok(number) => number,
+        Err(_) if literal.starts_with('-') => i64::MIN,
+        Err(_) => i64::MAX,
+    };
+    if number > NUMBER_MAX {
+        Some(format!(
+            "{} is too large for a NUMBER, which holds up to {}.",
+            literal, NUMBER_MAX
+        ))
+    } else if number < NUMBER_MIN {
+        Some(format!(
+            "{} is too small for a NUMBER, which holds down to {}.",
+            literal, NUMBER_MIN
+        ))
+    } else {
+        None
+    }
+}
+
+fn is_money_like(literal: &str) -> bool {
+    let mut chars = literal.chars();
+    chars.next() == Some('$') && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Checks the amount that starts at a `$` identifier, which may run on past
+/// it into the numbers and commas the parser split it into.
+fn check_money_at(node: Node, text: &str) -> Option<(Range, String)> {
+    let start = node.start_byte();
+    let length = text[start + 1..]
+        .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '.'))
+        .unwrap_or(text.len() - start - 1);
+    let amount = &text[start..start + 1 + length];
+    let mut message = check_money(amount)?;
+    if let Some(suggestion) = suggest_money(amount) {
+        message.push_str(&format!(" Did you mean {}?", suggestion));
+    }
+    let start_point = node.start_position();
+    let end_point = Point::new(start_point.row, start_point.column + amount.len());
+    let range = Range {
+        start: point_to_position(text, start_point, start),
+        end: point_to_position(text, end_point, start + amount.len()),
+    };
+    Some((range, message))
+}
+
+/// Why `amount` is not written the way PowerOn expects, such as
+/// `$1,234.56`, or None when it is.
+fn check_money(amount: &str) -> Option<String> {
+    let valid = amount
+        .strip_prefix('$')
+        .and_then(|amount| amount.split_once('.'))
+        .is_some_and(|(dollars, cents)| {
+            let mut groups = dollars.split(',');
+            let first = groups.next().unwrap_or_default();
+            let is_digits = |group: &str| group.bytes().all(|b| b.is_ascii_digit());
+            (1..=3).contains(&first.len())
+                && is_digits(first)
+                && groups.all(|group| group.len() == 3 && is_digits(group))
+                && cents.len() == 2
+                && is_digits(cents)
+        });
+    (!valid).then(|| {
+        format!(
+            "{} is not a money amount. Group the dollars with commas and write two decimal places.",
+            amount
+        )
+    })
+}
+
+/// `amount` written the way PowerOn expects, when it is clear what was meant.
+fn suggest_money(amount: &str) -> Option<String> {
+    let amount = amount.trim_start_matches('$').replace(',', "");
+    let (dollars, cents) = amount.split_once('.').unwrap_or((&amount, ""));
+    if dollars.is_empty() || cents.len() > 2 || cents.contains('.') {
+        return None;
+    }
+    let dollars = dollars.trim_start_matches('0');
+    let dollars = if dollars.is_empty() { "0" } else { dollars };
+    let mut grouped = String::new();
+    for (i, digit) in dollars.chars().enumerate() {
+        if i > 0 && (dollars.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    Some(format!("${}.{:0<2}", grouped, cents))
+}
+
+#[test]
+fn test_parse_date() {
+    assert_eq!(
+        Date::parse("'12/31/23'"),
+        Ok(Some(Date {
+            year: 2023,
+            month: 12,
+            day: 31
+        }))
+    );
+    assert_eq!(Date::parse("'--/--/--'"), Ok(None));
+    assert_eq!(
+        Date::parse("'02/29/2024'").unwrap().unwrap().to_string(),
+        "02/29/2024"
+    );
+    assert_eq!(
+        Date::parse("'02/29/2023'"),
+        Err("'02/29/2023' is not a date. 2/2023 has 28 days.".to_string())
+    );
+    assert_eq!(
+        Date::parse("'13/01/23'"),
+        Err("'13/01/23' has no month 13.".to_string())
+    );
+    assert_eq!(Date::parse("'01/01/99'").unwrap().unwrap().year, 1999);
+    assert_eq!(
+        Date::parse("'12/31/23'").unwrap().unwrap().weekday(),
+        "Sunday"
+    );
+    assert_eq!(
+        Date::parse("'02/29/2000'").unwrap().unwrap().weekday(),
+        "Tuesday"
+    );
+}
+
+#[test]
+fn test_literal_diagnostics() {
+    use crate::test_utils::diagnostic_lines;
+
+    let text = "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n B='02/30/2024'\n D=$1,234.56\n E=$1234.5\n \
+                G=150.5%\n H=5.25%\n I=99999999999\n J=0x1F\n K=2147483647\n LOW=-2147483648\n \
+                BELOW=-2147483649\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let diagnostics = get_literal_diagnostics(&tree, text);
+
+    assert_eq!(
+        diagnostic_lines(&diagnostics),
+        vec![
+            (2, "'02/30/2024' is not a date. 2/2024 has 29 days."),
+            (
+                4,
+                "$1234.5 is not a money amount. Group the dollars with commas and write two decimal places. Did you mean $1,234.50?"
+            ),
+            (5, "150.5% is out of range. Rates run from 0.000% to 100.000%."),
+            (
+                7,
+                "99999999999 is too large for a NUMBER, which holds up to 2147483647."
+            ),
+            (
+                8,
+                "0x1F is not a PowerOn number. Write numbers in decimal digits."
+            ),
+            (
+                11,
+                "-2147483649 is too small for a NUMBER, which holds down to -2147483648."
+            ),
+        ]
+    );
+    let ends: Vec<u32> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.range.end.character)
+        .collect();
+    assert_eq!(ends, vec![15, 10, 9, 14, 7, 18]);
+}
+
+#[test]
+fn test_check_money() {
+    assert_eq!(check_money("$1,234.56"), None);
+    assert_eq!(check_money("$0.50"), None);
+    assert_eq!(check_money("$1,000,000.00"), None);
+    assert!(check_money("$1234.56").is_some());
+    assert!(check_money("$12,34.56").is_some());
+    assert!(check_money("$5").is_some());
+}
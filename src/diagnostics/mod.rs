@@ -0,0 +1,142 @@
+pub mod debounce;
+pub mod duplicate_declarations;
+pub mod field_constraints;
+pub mod field_values;
+pub mod file_maintenance;
+pub mod function_calls;
+pub mod include_cycles;
+pub mod literals;
+pub mod record_nesting;
+
+use log::error;
+use tower_lsp::lsp_types::{Diagnostic, Url};
+
+use crate::{
+    cancellation::{Cancellable, CancellationToken},
+    index::{get_analysis_files, get_context_drivers},
+    state::Snapshot,
+};
+
+use self::{
+    duplicate_declarations::get_duplicate_declaration_diagnostics,
+    field_constraints::get_field_constraint_diagnostics,
+    file_maintenance::get_file_maintenance_diagnostics,
+    function_calls::get_function_call_diagnostics, include_cycles::get_include_cycle_diagnostics,
+    literals::get_literal_diagnostics, record_nesting::get_record_nesting_diagnostics,
+};
+
+/// The diagnostics of `uri`. They are memoized, so republishing the files
+/// an edit did not affect costs next to nothing.
+pub fn get_diagnostics(
+    snapshot: &Snapshot,
+    uri: &str,
+    token: &CancellationToken,
+) -> Cancellable<Vec<Diagnostic>> {
+    let diagnostics = snapshot.analysis.diagnostics(snapshot, uri, || {
+        let diagnostics = compute_diagnostics(snapshot, uri, token)?;
+        Ok((diagnostics, get_analysis_files(snapshot, uri)))
+    })?;
+    Ok(diagnostics.as_ref().clone())
+}
+
+fn compute_diagnostics(
+    snapshot: &Snapshot,
+    uri: &str,
+    token: &CancellationToken,
+) -> Cancellable<Vec<Diagnostic>> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let graph = &snapshot.include_graph;
+    let symbols = &snapshot.symbols;
+    diagnostics.extend(get_include_cycle_diagnostics(graph, uri));
+
+    diagnostics.extend(get_duplicate_declaration_diagnostics(
+        graph, symbols, uri, uri,
+    ));
+    // An include file is also checked in the context of the drivers that
+    // include it.
+    for driver in &get_context_drivers(snapshot, uri) {
+        token.check()?;
+        for diagnostic in get_duplicate_declaration_diagnostics(graph, symbols, driver, uri) {
+            if !diagnostics.iter().any(|d| d.range == diagnostic.range) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+    if let Some(document) = snapshot.document(uri) {
+        if let Some(tree) = document.tree() {
+            diagnostics.extend(get_literal_diagnostics(tree, &document.item.text));
+            diagnostics.extend(get_field_constraint_diagnostics(tree, &document.item.text));
+            let scope = snapshot.analysis.scope(snapshot, uri, token)?;
+            diagnostics.extend(get_function_call_diagnostics(
+                tree,
+                &document.item.text,
+                &scope,
+            ));
+        }
+    }
+    diagnostics.extend(get_record_diagnostics(snapshot, uri, token)?);
+    Ok(diagnostics)
+}
+
+/// What is current in a procedure depends on the driver calling it, so the
+/// record access and file maintenance of `uri` are checked once for each of
+/// its context drivers, or for `uri` itself when nothing includes it.
+fn get_record_diagnostics(
+    snapshot: &Snapshot,
+    uri: &str,
+    token: &CancellationToken,
+) -> Cancellable<Vec<Diagnostic>> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let document = match snapshot.document(uri) {
+        Some(document) => document,
+        None => return Ok(diagnostics),
+    };
+    let tree = match document.tree() {
+        Some(tree) => tree,
+        None => return Ok(diagnostics),
+    };
+    let drivers = match get_context_drivers(snapshot, uri) {
+        drivers if drivers.is_empty() => vec![uri.to_string()],
+        drivers => drivers,
+    };
+    let text = &document.item.text;
+    let scope = snapshot.analysis.scope(snapshot, uri, token)?;
+    for driver in &drivers {
+        token.check()?;
+        let procedures = snapshot
+            .analysis
+            .procedure_records(snapshot, driver, token)?;
+        let found = [
+            get_record_nesting_diagnostics(tree, text, &procedures),
+            get_file_maintenance_diagnostics(tree, text, &procedures, &scope),
+        ];
+        for diagnostic in found.into_iter().flatten() {
+            if !diagnostics.contains(&diagnostic) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+    Ok(diagnostics)
+}
+
+/// The diagnostics of every file in `uris`, or `Cancelled` as soon as the
+/// token is cancelled.
+pub fn collect_diagnostics(
+    snapshot: &Snapshot,
+    uris: &[String],
+    token: &CancellationToken,
+) -> Cancellable<Vec<(Url, Vec<Diagnostic>)>> {
+    let mut result: Vec<(Url, Vec<Diagnostic>)> = Vec::new();
+    for uri in uris {
+        token.check()?;
+        let url = match Url::parse(uri) {
+            Ok(url) => url,
+            Err(e) => {
+                error!("Error parsing file url: {}", e);
+                continue;
+            }
+        };
+        result.push((url, get_diagnostics(snapshot, uri, token)?));
+    }
+    Ok(result)
+}
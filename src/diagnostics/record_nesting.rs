@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use tree_sitter::{Node, Tree};
+
+use crate::{
+    database::record_types::{normalize_record_type, parent_record},
+    index::record_scope::{
+        field_record, procedure_name, scope_records, target_records, with_ancestry,
+    },
+    utils::node_range,
+};
+
+/// Reports `FOR EACH` loops over a record outside a loop over the record it
+/// belongs to, and fields read from a record that is not current. What is
+/// current in a procedure comes from `procedures`, as worked out for a
+/// driver by `get_procedure_records`. Procedures missing from it are not
+/// checked, and neither is the main body of a file without TARGET.
+pub fn get_record_nesting_diagnostics(
+    tree: &Tree,
+    text: &str,
+    procedures: &HashMap<String, Vec<String>>,
+) -> Vec<Diagnostic> {
+    let root = tree.root_node();
+    let records = target_records(root, text);
+    let checker = Checker {
+        text,
+        procedures,
+        procedure: None,
+    };
+    let mut diagnostics = Vec::new();
+    checker.check_node(
+        root,
+        (!records.is_empty()).then_some(&records),
+        &mut diagnostics,
+    );
+    diagnostics
+}
+
+struct Checker<'a> {
+    text: &'a str,
+    procedures: &'a HashMap<String, Vec<String>>,
+    /// The procedure being checked, or None for the main body.
+    procedure: Option<&'a str>,
+}
+
+impl Checker<'_> {
+    /// Checks `node` and everything under it, where `records` are current,
+    /// or are not known when None.
+    fn check_node(
+        &self,
+        node: Node,
+        records: Option<&Vec<String>>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        if node.kind() == "procedure_definition" {
+            self.check_procedure(node, diagnostics);
+            return;
+        }
+        let records = match records {
+            Some(records) => records,
+            None => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.check_node(child, None, diagnostics);
+                }
+                return;
+            }
+        };
+
+        match node.kind() {
+            "database_field" => self.check_field(node, records, diagnostics),
+            "foreachdoend" => check_loop(node, self.text, records, diagnostics),
+            _ => {}
+        }
+        let records = with_ancestry([records.to_vec(), scope_records(node, self.text)].concat());
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.check_node(child, Some(&records), diagnostics);
+        }
+    }
+
+    fn check_procedure(&self, node: Node, diagnostics: &mut Vec<Diagnostic>) {
+        let name = match procedure_name(node, self.text) {
+            Some(name) => name,
+            None => return,
+        };
+        let records = match self.procedures.get(&name) {
+            Some(records) => records,
+            None => return,
+        };
+        let checker = Checker {
+            procedure: Some(&name),
+            ..*self
+        };
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            checker.check_node(child, Some(records), diagnostics);
+        }
+    }
+
+    fn check_field(&self, node: Node, records: &[String], diagnostics: &mut Vec<Diagnostic>) {
+        let record = match field_record(node, self.text) {
+            Some(record) if !records.contains(&record) => record,
+            _ => return,
+        };
+        let place = match self.procedure {
+            Some(procedure) => format!("at every call to {}", procedure),
+            None => "here".to_string(),
+        };
+        diagnostics.push(error(
+            node,
+            self.text,
+            format!(
+                "{} is not current {}. Read its fields inside a FOR EACH {} loop or a WITH condition on it.",
+                record, place, record
+            ),
+        ));
+    }
+}
+
+fn check_loop(node: Node, text: &str, records: &[String], diagnostics: &mut Vec<Diagnostic>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() != "record_type" {
+            continue;
+        }
+        let record = match child.utf8_text(text.as_bytes()) {
+            Ok(record) => normalize_record_type(record),
+            Err(_) => continue,
+        };
+        let parent = match parent_record(&record) {
+            Some(parent) => parent,
+            None => continue,
+        };
+        if !records.contains(&parent) {
+            diagnostics.push(error(
+                child,
+                text,
+                format!(
+                    "{} records belong to a {}. Loop over them inside FOR EACH {}.",
+                    record, parent, parent
+                ),
+            ));
+        }
+    }
+}
+
+fn error(node: Node, text: &str, message: String) -> Diagnostic {
+    Diagnostic {
+        range: node_range(&node, text),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("pols".to_string()),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+#[test]
+fn test_record_nesting_diagnostics() {
+    let text = "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n FOR EACH SHARE WITH SHARE:TYPE=1\n  DO\n   \
+                FOR EACH SHARE TRANSFER\n    DO\n     PRINT SHARE TRANSFER:AMOUNT\n    END\n  END\n \
+                FOR EACH LOAN TRACKING\n  DO\n  END\n IF ANY SHARE WITH (SHARE:BALANCE>0) THEN\n  \
+                PRINT LOAN:BALANCE\n PRINT ACCOUNT:BRANCH\nEND\nPROCEDURE P\n PRINT LOAN:BALANCE\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let diagnostics = get_record_nesting_diagnostics(&tree, text, &HashMap::new());
+
+    let lines: Vec<u32> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.range.start.line)
+        .collect();
+    assert_eq!(lines, vec![9, 13]);
+    assert_eq!(
+        diagnostics[0].message,
+        "LOAN TRACKING records belong to a LOAN. Loop over them inside FOR EACH LOAN."
+    );
+    assert!(diagnostics[1]
+        .message
+        .starts_with("LOAN is not current here."));
+
+    let include = "PROCEDURE P\n FOR EACH LOAN TRACKING\n  DO\n  END\nEND\n";
+    let tree = crate::parser::parse(include).unwrap();
+    assert!(get_record_nesting_diagnostics(&tree, include, &HashMap::new()).is_empty());
+    let procedures = HashMap::from([("P".to_string(), vec!["ACCOUNT".to_string()])]);
+    let diagnostics = get_record_nesting_diagnostics(&tree, include, &procedures);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 1);
+}
+
+#[test]
+fn test_field_scope_in_procedures() {
+    let text = "TARGET=SHARE\nPRINT TITLE=\"X\"\n PRINT ACCOUNT:BRANCH\n CALL P\nEND\nPROCEDURE P\n \
+                PRINT SHARE:BALANCE\n PRINT LOAN:BALANCE\n FOR EACH LOAN\n  DO\n   PRINT LOAN:BALANCE\n  \
+                END\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let procedures = HashMap::from([(
+        "P".to_string(),
+        vec!["SHARE".to_string(), "ACCOUNT".to_string()],
+    )]);
+    let diagnostics = get_record_nesting_diagnostics(&tree, text, &procedures);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].range.start.line, 7);
+    assert!(diagnostics[0]
+        .message
+        .starts_with("LOAN is not current at every call to P."));
+}
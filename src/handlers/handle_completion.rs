@@ -1,73 +1,566 @@
+use std::collections::HashMap;
+
 use log::info;
 use tower_lsp::lsp_types::{
-    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionParams,
-    CompletionResponse, InsertTextFormat,
+    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionItemTag,
+    CompletionList, CompletionParams, CompletionResponse, InsertTextFormat,
 };
 
 use crate::{
-    completions::poweron_functions::POWERON_FUNCTION_COMPLETIONS,
-    database::{account_record_fields::ACCOUNT_RECORD_FIELDS, types::DatabaseField},
-    utils::word_at_point,
+    cancellation::CancellationToken,
+    completions::{
+        context::{completion_target, CompletionContext, Operand},
+        data::CompletionData,
+        keywords::STATEMENT_KEYWORD_COMPLETIONS,
+        ranking::{fuzzy_score, matches_word},
+    },
+    database::{
+        functions::BUILTIN_FUNCTIONS,
+        record_types::{child_records, record_fields, RECORD_TYPES},
+        system_variables::SYSTEM_VARIABLES,
+        types::{DataType, DatabaseField},
+    },
+    index::{
+        analysis::Scope, get_context_drivers, record_scope::records_at,
+        symbols::field_reference_key,
+    },
+    state::Snapshot,
+    text::{point_to_byte, position_to_point},
+    utils::get_basename_from_uri,
 };
 
-pub fn handle_comlpetion(params: &CompletionParams) -> Option<CompletionResponse> {
-    match &params.context {
-        Some(context) => {
-            match &context.trigger_character {
-                Some(trigger_character) => {
-                    if trigger_character == ":" {
-                        info!("Trigger character is :");
-                        return handle_database_field(&params);
-                    }
-                    return None;
-                }
-                None => return Some(CompletionResponse::Array(get_default_completions())),
+/// Ranks of completion items, best first. Declarations in the file being
+/// edited come before those from other files, which come before builtins.
+const LOCAL_RANK: u8 = 0;
+const INCLUDED_RANK: u8 = 1;
+const BUILTIN_RANK: u8 = 2;
+
+pub fn handle_comlpetion(
+    snapshot: &Snapshot,
+    params: &CompletionParams,
+    token: &CancellationToken,
+) -> Option<CompletionResponse> {
+    let uri = &params.text_document_position.text_document.uri;
+    let document = snapshot.document(uri.as_str())?;
+    let tree = document.tree()?;
+    let text = &document.item.text;
+    let point = position_to_point(text, params.text_document_position.position);
+    let target = completion_target(tree, text, point);
+    info!("completion context {:?}", target.context);
+
+    let trigger_character = params
+        .context
+        .as_ref()
+        .and_then(|context| context.trigger_character.as_deref());
+    if let Some(trigger_character) = trigger_character {
+        if !is_triggered_by(&target.context, trigger_character) {
+            return None;
+        }
+    }
+
+    let scope = || snapshot.analysis.scope(snapshot, uri.as_str(), token).ok();
+    let items = match target.context {
+        CompletionContext::TargetRecord => get_record_type_completions(),
+        CompletionContext::LoopRecord => {
+            // What is current in a procedure comes from the driver calling it.
+            let driver = get_context_drivers(snapshot, uri.as_str())
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| uri.to_string());
+            let procedures = snapshot
+                .analysis
+                .procedure_records(snapshot, &driver, token)
+                .ok()?;
+            match records_at(tree, text, point_to_byte(text, point), &procedures) {
+                Some(records) => get_loop_record_completions(&records),
+                None => get_record_type_completions(),
+            }
+        }
+        CompletionContext::WithCondition(records) => {
+            let mut items: Vec<CompletionItem> = get_record_type_completions()
+                .into_iter()
+                .filter(|item| records.contains(&item.label))
+                .map(|item| with_rank(item, LOCAL_RANK))
+                .collect();
+            items.extend(get_variable_completions(&*scope()?, uri.as_str()));
+            items.extend(get_default_completions());
+            items
+        }
+        CompletionContext::SystemVariable => get_system_variable_completions(),
+        CompletionContext::Field(record) => {
+            let expected = match &target.operand {
+                Some(Operand::Variable(name)) => scope()
+                    .and_then(|scope| scope.variable(name).and_then(|variable| variable.data_type)),
+                Some(Operand::Field(record, field)) => record_fields(record)
+                    .and_then(|fields| fields.get(field.to_lowercase().as_str()))
+                    .map(|field| field.data_type),
+                None => None,
             };
+            let items = get_field_completions(snapshot, &record, &target.prefix, expected, token)?;
+            // The order depends on what has been typed, so the client asks
+            // again as the word grows.
+            return Some(CompletionResponse::List(CompletionList {
+                is_incomplete: !target.prefix.is_empty(),
+                items,
+            }));
+        }
+        CompletionContext::DeclarationType => get_data_type_completions(),
+        CompletionContext::DeclarationName => return None,
+        CompletionContext::ProcedureName => get_procedure_completions(&*scope()?, uri.as_str()),
+        CompletionContext::Statement => {
+            let mut items = get_variable_completions(&*scope()?, uri.as_str());
+            items.extend(ranked(STATEMENT_KEYWORD_COMPLETIONS.clone(), BUILTIN_RANK));
+            items.extend(get_default_completions());
+            items
+        }
+        CompletionContext::FunctionArgument(_) | CompletionContext::Expression => {
+            let mut items = get_variable_completions(&*scope()?, uri.as_str());
+            items.extend(get_default_completions());
+            items.extend(ranked(get_record_type_completions(), BUILTIN_RANK));
+            items
         }
-        None => return Some(CompletionResponse::Array(get_default_completions())),
     };
+    Some(CompletionResponse::Array(items))
 }
 
+/// Trigger characters only open completion where they start something to
+/// complete, so typing `=` in an assignment does not list every field.
+fn is_triggered_by(context: &CompletionContext, trigger_character: &str) -> bool {
+    match trigger_character {
+        ":" => matches!(context, CompletionContext::Field(_)),
+        "=" => matches!(
+            context,
+            CompletionContext::TargetRecord
+                | CompletionContext::DeclarationType
+                | CompletionContext::Expression
+                | CompletionContext::FunctionArgument(_)
+        ),
+        "@" => *context == CompletionContext::SystemVariable,
+        _ => false,
+    }
+}
+
+/// The builtin functions, with what they return. Their documentation is left
+/// for resolve.
 fn get_default_completions() -> Vec<CompletionItem> {
-    let default_completions: Vec<CompletionItem> =
-        POWERON_FUNCTION_COMPLETIONS.values().cloned().collect();
-    default_completions
+    let default_completions: Vec<CompletionItem> = BUILTIN_FUNCTIONS
+        .values()
+        .map(|function| {
+            let item = CompletionItem {
+                label: function.name.to_string(),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: function.returns.describe(),
+                }),
+                kind: Some(CompletionItemKind::FUNCTION),
+                insert_text: Some(function.snippet()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                tags: function
+                    .deprecated
+                    .map(|_| vec![CompletionItemTag::DEPRECATED]),
+                ..CompletionItem::default()
+            };
+            let data = CompletionData::Function {
+                name: function.name.to_string(),
+            };
+            data.attach(item)
+        })
+        .collect();
+    ranked(default_completions, BUILTIN_RANK)
 }
 
-fn handle_database_field(params: &CompletionParams) -> Option<CompletionResponse> {
-    let line = &params.text_document_position.position.line;
-    let col = &params.text_document_position.position.character;
-    let uri = &params.text_document_position.text_document.uri;
-    let database_record = match word_at_point(uri, line, col) {
-        Some(database_record) => database_record.to_lowercase().replace(":", ""),
-        None => return None,
-    };
+/// Every variable visible from `uri`, once per name. A name declared in `uri`
+/// itself is described by that declaration.
+fn get_variable_completions(scope: &Scope, uri: &str) -> Vec<CompletionItem> {
+    let mut variables = HashMap::new();
+    for (file, variable) in scope.all_variables() {
+        let name = variable.name.to_uppercase();
+        if file == uri || !variables.contains_key(&name) {
+            variables.insert(name, (file, variable));
+        }
+    }
+    variables
+        .into_values()
+        .map(|(file, variable)| {
+            let item = CompletionItem {
+                label: variable.name.to_uppercase(),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(variable.type_description()),
+                }),
+                kind: Some(CompletionItemKind::VARIABLE),
+                detail: Some(format!(
+                    "{}={}, declared in {}",
+                    variable.name,
+                    variable.type_description(),
+                    get_basename_from_uri(file)
+                )),
+                ..CompletionItem::default()
+            };
+            with_rank(item, declaration_rank(file, uri))
+        })
+        .collect()
+}
 
-    match database_record.as_str() {
-        "account" => {
-            let account_fields: Vec<DatabaseField> =
-                ACCOUNT_RECORD_FIELDS.values().cloned().collect();
-            let account_fields: Vec<CompletionItem> = account_fields
-                .iter()
-                .map(|field| {
-                    let completion_item = CompletionItem {
-                        label: field.mnemonic.clone().to_uppercase(),
-                        label_details: Some(CompletionItemLabelDetails {
-                            detail: None,
-                            description: Some(field.description.to_string()),
-                        }),
-                        kind: Some(CompletionItemKind::FIELD),
-                        detail: None,
-                        deprecated: Some(false),
-                        insert_text: Some(field.mnemonic.clone().to_string().to_uppercase()),
-                        insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
-                        ..CompletionItem::default()
-                    };
-                    completion_item
-                })
-                .collect();
-            return Some(CompletionResponse::Array(account_fields));
+fn get_procedure_completions(scope: &Scope, uri: &str) -> Vec<CompletionItem> {
+    let mut procedures = HashMap::new();
+    for (file, procedure) in scope.all_procedures() {
+        let name = procedure.name.to_uppercase();
+        if file == uri || !procedures.contains_key(&name) {
+            procedures.insert(name, (file, procedure));
         }
-        _ => return None,
+    }
+    procedures
+        .into_values()
+        .map(|(file, procedure)| {
+            let item = CompletionItem {
+                label: procedure.name.to_uppercase(),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(get_basename_from_uri(file)),
+                }),
+                kind: Some(CompletionItemKind::FUNCTION),
+                detail: Some(format!(
+                    "PROCEDURE {}, defined in {}",
+                    procedure.name,
+                    get_basename_from_uri(file)
+                )),
+                ..CompletionItem::default()
+            };
+            with_rank(item, declaration_rank(file, uri))
+        })
+        .collect()
+}
+
+fn declaration_rank(file: &str, uri: &str) -> u8 {
+    if file == uri {
+        LOCAL_RANK
+    } else {
+        INCLUDED_RANK
+    }
+}
+
+fn ranked(items: Vec<CompletionItem>, rank: u8) -> Vec<CompletionItem> {
+    items
+        .into_iter()
+        .map(|item| with_rank(item, rank))
+        .collect()
+}
+
+/// Orders `item` by `rank`, then by label, as clients sort on `sort_text`.
+fn with_rank(mut item: CompletionItem, rank: u8) -> CompletionItem {
+    item.sort_text = Some(format!("{}{}", rank, item.label));
+    item
+}
+
+fn get_record_type_completions() -> Vec<CompletionItem> {
+    RECORD_TYPES
+        .iter()
+        .map(|record_type| CompletionItem {
+            label: record_type.to_string(),
+            kind: Some(CompletionItemKind::STRUCT),
+            detail: Some("Record".to_string()),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// The records that belong to one of `records`, which are current where a
+/// loop over them starts.
+fn get_loop_record_completions(records: &[String]) -> Vec<CompletionItem> {
+    let children: Vec<&str> = records
+        .iter()
+        .flat_map(|record| child_records(record))
+        .collect();
+    get_record_type_completions()
+        .into_iter()
+        .filter(|item| children.contains(&item.label.as_str()))
+        .collect()
+}
+
+/// System variables are inserted without the `@` the user already typed,
+/// which editors do not count as part of the word.
+fn get_system_variable_completions() -> Vec<CompletionItem> {
+    SYSTEM_VARIABLES
+        .values()
+        .map(|variable| {
+            let item = CompletionItem {
+                label: format!("@{}", variable.name),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(variable.data_type.as_str().to_string()),
+                }),
+                kind: Some(CompletionItemKind::VARIABLE),
+                detail: Some(format!(
+                    "@{}={}",
+                    variable.name,
+                    variable.data_type.as_str()
+                )),
+                filter_text: Some(variable.name.clone()),
+                insert_text: Some(variable.name.clone()),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..CompletionItem::default()
+            };
+            let data = CompletionData::SystemVariable {
+                name: variable.name.clone(),
+            };
+            data.attach(item)
+        })
+        .collect()
+}
+
+fn get_data_type_completions() -> Vec<CompletionItem> {
+    DataType::ALL
+        .iter()
+        .map(|data_type| CompletionItem {
+            label: data_type.as_str().to_string(),
+            kind: Some(CompletionItemKind::TYPE_PARAMETER),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// The fields of `record` that match `prefix`, described by their short
+/// description. Fields of the `expected` type come first, then the closest
+/// matches, then the fields the workspace uses most. Fields of other types
+/// are still offered, last. The full help text is left for resolve.
+fn get_field_completions(
+    snapshot: &Snapshot,
+    record: &str,
+    prefix: &str,
+    expected: Option<DataType>,
+    token: &CancellationToken,
+) -> Option<Vec<CompletionItem>> {
+    let field_uses = snapshot.analysis.field_uses(snapshot, token).ok()?;
+    let mut fields: Vec<(bool, u32, u32, &DatabaseField)> = record_fields(record)?
+        .values()
+        .filter_map(|field| {
+            let score = match prefix {
+                "" => 0,
+                _ => fuzzy_score(prefix, field.mnemonic)
+                    .or_else(|| matches_word(prefix, field.description).then_some(0))?,
+            };
+            let mismatched =
+                expected.is_some_and(|expected| !expected.is_compatible_with(field.data_type));
+            let uses = field_uses
+                .get(&field_reference_key(record, field.mnemonic))
+                .copied()
+                .unwrap_or_default();
+            Some((mismatched, score, uses, field))
+        })
+        .collect();
+    fields.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(b.1.cmp(&a.1))
+            .then(b.2.cmp(&a.2))
+            .then(a.3.mnemonic.cmp(b.3.mnemonic))
+    });
+
+    let fields = fields
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, _, _, field))| {
+            let mnemonic = field.mnemonic.to_uppercase();
+            let item = CompletionItem {
+                label: mnemonic.clone(),
+                label_details: Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(field.description.to_string()),
+                }),
+                kind: Some(CompletionItemKind::FIELD),
+                detail: None,
+                deprecated: Some(false),
+                sort_text: Some(format!("{:04}", i)),
+                filter_text: Some(format!("{} {}", mnemonic, field.description)),
+                insert_text: Some(mnemonic),
+                insert_text_format: Some(InsertTextFormat::PLAIN_TEXT),
+                ..CompletionItem::default()
+            };
+            let data = CompletionData::Field {
+                record: record.to_string(),
+                mnemonic: field.mnemonic.to_string(),
+            };
+            data.attach(item)
+        })
+        .collect();
+    Some(fields)
+}
+
+#[cfg(test)]
+use crate::test_utils::snapshot_with;
+
+#[cfg(test)]
+fn complete_at(
+    snapshot: &Snapshot,
+    uri: &str,
+    line: u32,
+    character: u32,
+    trigger: Option<&str>,
+) -> Option<Vec<CompletionItem>> {
+    use tower_lsp::lsp_types::{
+        CompletionContext as TriggerContext, CompletionTriggerKind, Position,
+        TextDocumentIdentifier, TextDocumentPositionParams, Url,
+    };
+
+    let params = CompletionParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: Url::parse(uri).unwrap(),
+            },
+            position: Position { line, character },
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: Some(TriggerContext {
+            trigger_kind: CompletionTriggerKind::TRIGGER_CHARACTER,
+            trigger_character: trigger.map(|c| c.to_string()),
+        }),
+    };
+    match handle_comlpetion(snapshot, &params, &CancellationToken::new()) {
+        Some(CompletionResponse::Array(items)) => Some(items),
+        Some(CompletionResponse::List(list)) => Some(list.items),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+fn labels(items: Vec<CompletionItem>) -> Vec<String> {
+    items.into_iter().map(|item| item.label).collect()
+}
+
+#[test]
+fn test_completion_by_context() {
+    let uri = "file:///specs/DRIVER";
+    let text = "TARGET=ACCOUNT\nDEFINE\n X=\nEND\nPRINT TITLE=\"X\"\n  IF ACCOUNT:\nEND\n";
+    let snapshot = snapshot_with(&[(uri, text)]);
+    let complete = |line, character, trigger| complete_at(&snapshot, uri, line, character, trigger);
+
+    let fields = labels(complete(5, 13, Some(":")).unwrap());
+    assert!(fields.contains(&"OPENDATE".to_string()));
+    assert!(!fields.contains(&"ABS".to_string()));
+    let types = labels(complete(2, 3, None).unwrap());
+    assert!(types.contains(&"MONEY".to_string()));
+    assert!(!types.contains(&"OPENDATE".to_string()));
+    let records = labels(complete(0, 7, None).unwrap());
+    assert!(records.contains(&"SHARE TRANSFER".to_string()));
+    assert!(!records.contains(&"IF".to_string()));
+    let statements = labels(complete(5, 2, None).unwrap());
+    assert!(statements.contains(&"IF".to_string()));
+    assert!(complete(3, 0, Some(":")).is_none());
+}
+
+#[test]
+fn test_completion_triggers() {
+    let uri = "file:///specs/DRIVER";
+    let text = "TARGET=\nPRINT TITLE=\"X\"\n X=@\n FOR EACH SHARE WITH \n DO\n END\nEND\n";
+    let snapshot = snapshot_with(&[(uri, text)]);
+    let complete = |line, character, trigger| complete_at(&snapshot, uri, line, character, trigger);
+
+    let records = labels(complete(0, 7, Some("=")).unwrap());
+    assert!(records.contains(&"ACCOUNT".to_string()));
+    let variables = complete(2, 4, Some("@")).unwrap();
+    let envarg = variables
+        .iter()
+        .find(|item| item.label == "@ENVARGCHAR")
+        .unwrap();
+    assert_eq!(envarg.insert_text.as_deref(), Some("ENVARGCHAR"));
+    assert!(labels(variables).contains(&"@USERCHR1".to_string()));
+    assert!(complete(2, 4, Some("=")).is_none());
+
+    let mut items = complete(3, 21, None).unwrap();
+    items.sort_by(|a, b| a.sort_text.cmp(&b.sort_text));
+    assert_eq!(items[0].label, "SHARE");
+    assert!(!labels(items).contains(&"LOAN".to_string()));
+}
+
+#[test]
+fn test_completion_of_declarations_in_scope() {
+    let driver = "file:///specs/DRIVER";
+    let snapshot = snapshot_with(&[
+        (
+            driver,
+            "TARGET=ACCOUNT\nDEFINE\n #INCLUDE \"COMMON.DEF\"\n TOTAL=MONEY\nEND\n\
+             PRINT TITLE=\"X\"\n X=\n CALL \nEND\n#INCLUDE \"HEADER.PRO\"\n",
+        ),
+        ("file:///specs/COMMON.DEF", "TRUE=1\nNAME=CHARACTER(40)\n"),
+        ("file:///specs/HEADER.PRO", "PROCEDURE PRINTHEADER\nEND\n"),
+    ]);
+
+    let items = complete_at(&snapshot, driver, 6, 3, None).unwrap();
+    let item = |label: &str| items.iter().find(|item| item.label == label).unwrap();
+    assert_eq!(
+        item("TOTAL").detail.as_deref(),
+        Some("TOTAL=MONEY, declared in DRIVER")
+    );
+    assert_eq!(
+        item("NAME").detail.as_deref(),
+        Some("NAME=CHARACTER(40), declared in COMMON.DEF")
+    );
+    let mut sorted = items.clone();
+    sorted.sort_by(|a, b| a.sort_text.cmp(&b.sort_text));
+    assert_eq!(labels(sorted[..3].to_vec()), vec!["TOTAL", "NAME", "TRUE"]);
+    assert!(labels(items).contains(&"ABS".to_string()));
+
+    let procedures = labels(complete_at(&snapshot, driver, 7, 6, None).unwrap());
+    assert_eq!(procedures, vec!["PRINTHEADER"]);
+}
+
+#[test]
+fn test_field_completion_ranking() {
+    let driver = "file:///specs/DRIVER";
+    let snapshot = snapshot_with(&[
+        (
+            driver,
+            "TARGET=ACCOUNT\nDEFINE\n D=DATE\n M=MONEY\nEND\nPRINT TITLE=\"X\"\n D=ACCOUNT:\n \
+             M=ACCOUNT:\n X=ACCOUNT:open\nEND\n",
+        ),
+        (
+            "file:///specs/CLOSED",
+            "PRINT TITLE=\"X\"\n PRINT ACCOUNT:CLOSEDATE\n PRINT ACCOUNT:CLOSEDATE\nEND\n",
+        ),
+    ]);
+    let sorted = |line, character| {
+        let mut items = complete_at(&snapshot, driver, line, character, None).unwrap();
+        items.sort_by(|a, b| a.sort_text.cmp(&b.sort_text));
+        items
     };
+    let field_type =
+        |label: &str| record_fields("ACCOUNT").unwrap()[label.to_lowercase().as_str()].data_type;
+
+    let dates = sorted(6, 11);
+    assert_eq!(dates[0].label, "CLOSEDATE");
+    let date_count = dates
+        .iter()
+        .take_while(|item| field_type(&item.label) == DataType::Date)
+        .count();
+    assert!(date_count > 1);
+    assert!(dates[date_count..]
+        .iter()
+        .all(|item| field_type(&item.label) != DataType::Date));
+
+    let money = sorted(7, 11);
+    assert_eq!(field_type(&money[0].label), DataType::Money);
+
+    let open = sorted(8, 15);
+    assert_eq!(open[0].label, "OPENDATE");
+    assert!(open.len() < dates.len());
+}
+
+#[test]
+fn test_loop_record_completion() {
+    let uri = "file:///specs/DRIVER";
+    let text = "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n FOR EACH \n FOR EACH LOAN\n  DO\n   \
+                FOR EACH \n  END\nEND\n";
+    let snapshot = snapshot_with(&[(uri, text)]);
+
+    let records = labels(complete_at(&snapshot, uri, 2, 10, None).unwrap());
+    assert!(records.contains(&"SHARE".to_string()));
+    assert!(records.contains(&"NAME".to_string()));
+    assert!(!records.contains(&"LOAN TRACKING".to_string()));
+    assert!(!records.contains(&"GLACCOUNT".to_string()));
+
+    let records = labels(complete_at(&snapshot, uri, 5, 12, None).unwrap());
+    assert!(records.contains(&"LOAN TRACKING".to_string()));
+    assert!(records.contains(&"SHARE".to_string()));
+    assert!(!records.contains(&"SHARE TRANSFER".to_string()));
 }
@@ -0,0 +1,104 @@
+use log::info;
+use tower_lsp::lsp_types::{CompletionItem, Documentation, MarkupContent, MarkupKind};
+
+use crate::{
+    completions::data::CompletionData,
+    database::{
+        functions::BUILTIN_FUNCTIONS, record_types::record_fields,
+        system_variables::SYSTEM_VARIABLES,
+    },
+};
+
+/// Attaches the documentation completion lists leave out. Items without data,
+/// or whose data no longer matches the catalogs, are returned unchanged.
+pub fn handle_completion_resolve(item: CompletionItem) -> CompletionItem {
+    info!("received completionItem/resolve request");
+    let data = match CompletionData::from_item(&item) {
+        Some(data) => data,
+        None => return item,
+    };
+    let resolved = match &data {
+        CompletionData::Field { record, mnemonic } => resolve_field(&item, record, mnemonic),
+        CompletionData::Function { name } => resolve_function(&item, name),
+        CompletionData::SystemVariable { name } => resolve_system_variable(&item, name),
+    };
+    resolved.unwrap_or(item)
+}
+
+fn resolve_field(item: &CompletionItem, record: &str, mnemonic: &str) -> Option<CompletionItem> {
+    let field = record_fields(record)?.get(mnemonic)?;
+    Some(CompletionItem {
+        detail: Some(format!(
+            "{}:{}, field {}, {}",
+            record,
+            field.mnemonic.to_uppercase(),
+            field.field_number,
+            field.type_description()
+        )),
+        documentation: Some(markdown(field.details.trim())),
+        ..item.clone()
+    })
+}
+
+fn resolve_function(item: &CompletionItem, name: &str) -> Option<CompletionItem> {
+    let function = BUILTIN_FUNCTIONS.get(name)?;
+    Some(CompletionItem {
+        detail: Some(function.signature()),
+        documentation: Some(markdown(&function.documentation())),
+        ..item.clone()
+    })
+}
+
+fn resolve_system_variable(item: &CompletionItem, name: &str) -> Option<CompletionItem> {
+    let variable = SYSTEM_VARIABLES.get(name)?;
+    Some(CompletionItem {
+        documentation: Some(Documentation::String(variable.description.clone())),
+        ..item.clone()
+    })
+}
+
+fn markdown(value: &str) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: value.to_string(),
+    })
+}
+
+#[test]
+fn test_completion_resolve() {
+    let field = CompletionData::Field {
+        record: "ACCOUNT".to_string(),
+        mnemonic: "branch".to_string(),
+    }
+    .attach(CompletionItem {
+        label: "BRANCH".to_string(),
+        ..CompletionItem::default()
+    });
+    let field = handle_completion_resolve(field);
+    assert_eq!(
+        field.detail.as_deref(),
+        Some("ACCOUNT:BRANCH, field 7, Code to 9999")
+    );
+    match field.documentation {
+        Some(Documentation::MarkupContent(content)) => {
+            assert!(content.value.starts_with("# Branch"))
+        }
+        documentation => panic!("unexpected documentation {:?}", documentation),
+    }
+
+    let function = CompletionData::Function {
+        name: "ABS".to_string(),
+    }
+    .attach(CompletionItem {
+        label: "ABS".to_string(),
+        ..CompletionItem::default()
+    });
+    let function = handle_completion_resolve(function);
+    assert_eq!(function.detail.as_deref(), Some("ABS(Expression)"));
+
+    let unknown = CompletionItem {
+        label: "X".to_string(),
+        ..CompletionItem::default()
+    };
+    assert_eq!(handle_completion_resolve(unknown.clone()), unknown);
+}
@@ -1,12 +1,11 @@
 use log::error;
-use tower_lsp::lsp_types::{
-    GotoDefinitionParams, GotoDefinitionResponse, Location, TextDocumentItem,
-};
-use tree_sitter::{Point, Query, QueryCursor};
+use tower_lsp::lsp_types::{GotoDefinitionParams, GotoDefinitionResponse, Location, Range, Url};
 
 use crate::{
-    lsp::CONTEXT,
-    utils::{get_inc_files, is_def_file, is_poweron_driver},
+    cancellation::CancellationToken,
+    index::{file_kind::FileKind, get_drivers, get_file_kind},
+    state::Snapshot,
+    text::position_to_point,
 };
 
 #[derive(Clone, Debug)]
@@ -15,39 +14,44 @@ pub enum QueryType {
     ProcedureCall,
 }
 
-pub fn handle_definition(params: &GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+pub fn handle_definition(
+    snapshot: &Snapshot,
+    params: &GotoDefinitionParams,
+    token: &CancellationToken,
+) -> Option<GotoDefinitionResponse> {
     let mut result: Vec<Location> = Vec::new();
-    let line = params.text_document_position_params.position.line as usize;
-    let col = params.text_document_position_params.position.character as usize;
-    let p = Point::new(line, col);
-    let trees = match CONTEXT.trees.lock() {
-        Ok(trees) => trees.clone(),
-        Err(e) => {
-            error!("error getting trees lock: {}", e);
-            return None;
-        }
-    };
-    let tree = match trees.get(
-        &params
+    let document = match snapshot.document(
+        params
             .text_document_position_params
             .text_document
             .uri
-            .to_string(),
+            .as_str(),
     ) {
-        Some(tree) => tree.clone(),
+        Some(document) => document,
+        None => {
+            error!(
+                "error getting document for uri: {}",
+                params.text_document_position_params.text_document.uri
+            );
+            return None;
+        }
+    };
+    let tree = match document.tree() {
+        Some(tree) => tree,
         None => {
             error!(
-                "error getting tree for uri: {} on line {}",
-                params.text_document_position_params.text_document.uri, 42
+                "error getting tree for uri: {}",
+                params.text_document_position_params.text_document.uri
             );
             return None;
         }
     };
 
+    let p = position_to_point(
+        &document.item.text,
+        params.text_document_position_params.position,
+    );
     let node_to_find = tree.root_node().descendant_for_point_range(p, p);
-    if node_to_find.is_none() {
-        return None;
-    }
     let node_to_find = match node_to_find {
         Some(node) => node,
         None => {
@@ -59,36 +63,7 @@ pub fn handle_definition(params: &GotoDefinitionParams) -> Option<GotoDefinition
         return None;
     }
 
-    let documents = match CONTEXT.documents.lock() {
-        Ok(documents) => documents.clone(),
-        Err(e) => {
-            error!("error getting documents lock: {}", e);
-            return None;
-        }
-    };
-
-    let document = match documents.get(
-        &params
-            .text_document_position_params
-            .text_document
-            .uri
-            .to_string(),
-    ) {
-        Some(document) => document.clone(),
-        None => {
-            error!(
-                "error getting document for uri: {}",
-                params.text_document_position_params.text_document.uri
-            );
-            return None;
-        }
-    };
-
-    if is_def_file(&document) {
-        return None;
-    }
-
-    let source = &document.text.as_bytes();
+    let source = &document.item.text.as_bytes();
     let mut query_type: QueryType = QueryType::Identifier;
     let mut declaration_to_find = match node_to_find.utf8_text(source) {
         Ok(text) => text,
@@ -101,9 +76,6 @@ pub fn handle_definition(params: &GotoDefinitionParams) -> Option<GotoDefinition
     if node_to_find.kind() == "procedure_call" {
         query_type = QueryType::ProcedureCall;
         let child = node_to_find.child_by_field_name("identifier");
-        if child.is_none() {
-            return None;
-        }
         let child_to_find = match child {
             Some(child) => child,
             None => {
@@ -133,175 +105,128 @@ pub fn handle_definition(params: &GotoDefinitionParams) -> Option<GotoDefinition
         }
     }
 
-    result.append(&mut search_current_file(
-        &document,
-        query_type.clone(),
+    let uri = document.item.uri.as_str();
+    result.append(&mut search_file(
+        snapshot,
+        uri,
+        &query_type,
         declaration_to_find,
     ));
 
-    if result.len() > 0 {
+    if !result.is_empty() {
         return Some(GotoDefinitionResponse::Array(result));
     }
 
-    let files_to_search = get_files_to_seach(&document);
-
-    for file in files_to_search {
-        let documents = match CONTEXT.documents.lock() {
-            Ok(documents) => documents.clone(),
-            Err(e) => {
-                error!("error getting documents lock: {}", e);
-                return None;
-            }
-        };
-        let document = match documents.get(&file) {
-            Some(document) => document.clone(),
-            None => {
-                error!("error getting document for uri: {}", file);
-                return None;
-            }
-        };
-        result.append(&mut search_current_file(
-            &document,
-            query_type.clone(),
-            declaration_to_find,
-        ))
-    }
-
+    result.append(&mut search_other_files(
+        snapshot,
+        token,
+        uri,
+        &query_type,
+        declaration_to_find,
+    ));
     Some(GotoDefinitionResponse::Array(result))
 }
 
-fn search_current_file(
-    document: &TextDocumentItem,
-    query_type: QueryType,
+/// The declarations of `name` made by `uri` itself, read from the index.
+fn search_file(
+    snapshot: &Snapshot,
+    uri: &str,
+    query_type: &QueryType,
     declaration_to_find: &str,
 ) -> Vec<Location> {
-    let mut result: Vec<Location> = Vec::new();
-    let source = document.text.as_str();
-    let trees = match CONTEXT.trees.lock() {
-        Ok(trees) => trees.clone(),
-        Err(e) => {
-            error!("error getting trees lock: {}", e);
-            return result;
-        }
+    let file_symbols = match snapshot.symbols.get(uri) {
+        Some(file_symbols) => file_symbols,
+        None => return Vec::new(),
     };
-
-    let tree = match trees.get(&document.uri.to_string()) {
-        Some(tree) => tree.clone(),
-        None => {
-            error!(
-                "error getting tree for uri: {} on line {}",
-                document.uri, 196
-            );
-            return result;
-        }
-    };
-
-    let lang = tree.language();
-    let mut cursor = QueryCursor::new();
-    let query_string = match query_type {
-        QueryType::Identifier => "(variable_declaration (identifier) @ident)",
-        QueryType::ProcedureCall => "(procedure_definition (identifier) @ident)",
-    };
-
-    let query = match Query::new(lang, query_string) {
-        Ok(query) => query,
+    let url = match Url::parse(uri) {
+        Ok(url) => url,
         Err(e) => {
-            error!("error creating query: {}", e);
-            return result;
+            error!("error parsing uri {}: {}", uri, e);
+            return Vec::new();
         }
     };
-
-    let matches = cursor.matches(&query, tree.root_node(), source.as_bytes());
-    matches.for_each(|m| {
-        let ident = match m.captures[0].node.utf8_text(source.as_bytes()) {
-            Ok(ident) => ident,
-            Err(e) => {
-                error!("error getting utf8 text: {}", e);
-                return;
-            }
-        };
-        if ident == declaration_to_find {
-            // we found a match in this file return it.
-            let start = m.captures[0].node.start_position();
-            let end = m.captures[0].node.end_position();
-            let location = Location {
-                uri: document.uri.clone(),
-                range: tower_lsp::lsp_types::Range {
-                    start: tower_lsp::lsp_types::Position {
-                        line: start.row as u32,
-                        character: start.column as u32,
-                    },
-                    end: tower_lsp::lsp_types::Position {
-                        line: end.row as u32,
-                        character: end.column as u32,
-                    },
-                },
-            };
-
-            result.push(location);
-        }
-    });
-    result
+    let ranges: Vec<Range> = match query_type {
+        QueryType::Identifier => file_symbols
+            .variables
+            .iter()
+            .filter(|variable| variable.name.eq_ignore_ascii_case(declaration_to_find))
+            .map(|variable| variable.range)
+            .collect(),
+        QueryType::ProcedureCall => file_symbols
+            .procedures
+            .iter()
+            .filter(|procedure| procedure.name.eq_ignore_ascii_case(declaration_to_find))
+            .map(|procedure| procedure.range)
+            .collect(),
+    };
+    ranges
+        .into_iter()
+        .map(|range| Location {
+            uri: url.clone(),
+            range,
+        })
+        .collect()
 }
 
-fn get_files_to_seach(document: &TextDocumentItem) -> Vec<String> {
-    let mut files_to_search: Vec<String> = Vec::new();
+fn search_other_files(
+    snapshot: &Snapshot,
+    token: &CancellationToken,
+    uri: &str,
+    query_type: &QueryType,
+    declaration_to_find: &str,
+) -> Vec<Location> {
     // Attempting to be smart if the poweron is a 'driver' file. In this case,
     // we only find the definition if it is in the same file or in an include file.
     // this should alievieate finding multiple definitions in the same workspace for
     // common vars like true/false
-    if is_poweron_driver(&document) {
-        // find all inc files
-        let mut searched_files: Vec<String> = Vec::new();
-        let inc_files = get_inc_files(&document);
-        searched_files.push(document.uri.to_string());
-        // search 3 levels deep
-        for _ in 1..3 {
-            inc_files.iter().for_each(|f| {
-                if !searched_files.contains(f) {
-                    let doc = match CONTEXT.documents.lock() {
-                        Ok(documents) => {
-                            let doc = match documents.get(f) {
-                                Some(doc) => doc.clone(),
-                                None => {
-                                    error!("error getting document for uri: {}", f);
-                                    return;
-                                }
-                            };
-                            doc
-                        }
-                        Err(e) => {
-                            error!("error getting documents lock: {}", e);
-                            return;
-                        }
-                    };
-                    let mut inc_files = get_inc_files(&doc);
-                    if !files_to_search.contains(f) {
-                        files_to_search.push(f.to_string());
-                    }
-                    if inc_files.len() > 0 {
-                        searched_files.push(f.to_string());
-                        files_to_search.append(&mut inc_files);
-                    }
-                }
-            });
-        }
-    } else {
-        // not driver file search all files in the workspace
-        let documents = match CONTEXT.documents.lock() {
-            Ok(documents) => documents.clone(),
-            Err(e) => {
-                error!("error getting documents lock: {}", e);
-                return Vec::new();
-            }
+    //
+    // Include files are searched in the context of the drivers that include
+    // them, so a PRO file resolves variables from the DEF files its driver
+    // includes.
+    let is_driver = get_file_kind(snapshot, uri) == Some(FileKind::Driver);
+    if is_driver || !get_drivers(snapshot, uri).is_empty() {
+        let scope = match snapshot.analysis.scope(snapshot, uri, token) {
+            Ok(scope) => scope,
+            Err(_) => return Vec::new(),
         };
-
-        documents.iter().for_each(|(uri, _)| {
-            if !files_to_search.contains(uri) {
-                files_to_search.push(uri.to_string());
-            }
-        });
+        let declarations: Vec<(&String, Range)> = match query_type {
+            QueryType::Identifier => scope
+                .variables(declaration_to_find)
+                .iter()
+                .map(|(file, variable)| (file, variable.range))
+                .collect(),
+            QueryType::ProcedureCall => scope
+                .procedures(declaration_to_find)
+                .iter()
+                .map(|(file, procedure)| (file, procedure.range))
+                .collect(),
+        };
+        return declarations
+            .into_iter()
+            .filter(|(file, _)| file.as_str() != uri)
+            .filter_map(|(file, range)| {
+                Some(Location {
+                    uri: Url::parse(file).ok()?,
+                    range,
+                })
+            })
+            .collect();
     }
 
-    files_to_search
+    // not driver file search all files in the workspace
+    let mut result: Vec<Location> = Vec::new();
+    for file in snapshot.symbols.keys() {
+        if token.is_cancelled() {
+            break;
+        }
+        if file != uri {
+            result.append(&mut search_file(
+                snapshot,
+                file,
+                query_type,
+                declaration_to_find,
+            ));
+        }
+    }
+    result
 }
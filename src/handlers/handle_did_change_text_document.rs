@@ -1,47 +1,78 @@
 use log::info;
 use tower_lsp::lsp_types::{DidChangeTextDocumentParams, TextDocumentItem};
 
-use crate::{lsp::CONTEXT, utils::analyze};
+use crate::{
+    index::{index_document, uncache_document},
+    state::{Document, ServerState},
+};
 
-pub fn handle_did_change_text_document(params: &DidChangeTextDocumentParams) {
+pub fn handle_did_change_text_document(state: &ServerState, params: &DidChangeTextDocumentParams) {
     info!("received didChangeTextDocument notification");
-    // find the document and the tree
-    let mut documents = match CONTEXT.documents.lock() {
-        Ok(documents) => documents,
-        Err(e) => {
-            info!("failed to lock documents: {}", e);
-            return;
-        }
+    // With full sync the last change holds the whole text.
+    let change = match params.content_changes.last() {
+        Some(change) => change,
+        None => return,
     };
-
-    let document = &mut TextDocumentItem {
+    let uri = params.text_document.uri.as_str();
+    let document = TextDocumentItem {
         uri: params.text_document.uri.clone(),
         language_id: "poweron".to_string(),
         version: params.text_document.version,
-        text: params.content_changes[0].text.clone(),
+        text: change.text.clone(),
     };
-    documents.insert(params.text_document.uri.to_string(), document.clone());
-    let mut trees = match CONTEXT.trees.lock() {
-        Ok(trees) => trees,
-        Err(e) => {
-            info!("failed to lock trees: {}", e);
-            return;
+    // Parse before taking the edit lock, so other edits are not held up.
+    let document = Document::parsed(document);
+    if document.tree().is_none() {
+        info!("failed to parse document");
+    }
+    // Changes are parsed on blocking threads, so a later version may have
+    // been indexed already.
+    state.edit(|snapshot| {
+        let newer = snapshot
+            .document(uri)
+            .is_some_and(|current| current.item.version > document.item.version);
+        if !newer {
+            index_document(snapshot, document);
         }
+    });
+    uncache_document(state, uri);
+}
+
+#[test]
+fn test_did_change_ignores_empty_and_stale_changes() {
+    use tower_lsp::lsp_types::{
+        TextDocumentContentChangeEvent, Url, VersionedTextDocumentIdentifier,
     };
-    let tree = analyze(document);
-    match tree {
-        Ok(tree) => match tree {
-            Some(tree) => {
-                trees.insert(params.text_document.uri.to_string(), tree);
-            }
-            None => {
-                info!("failed to parse document");
-                return;
-            }
+
+    let state = ServerState::new();
+    let uri = Url::parse("file:///specs/COMMON.DEF").unwrap();
+    let change = |version, texts: &[&str]| DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier {
+            uri: uri.clone(),
+            version,
         },
-        Err(e) => {
-            info!("failed to parse document: {}", e);
-            return;
-        }
-    }
+        content_changes: texts
+            .iter()
+            .map(|text| TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: text.to_string(),
+            })
+            .collect(),
+    };
+    let text = || {
+        state
+            .snapshot()
+            .document(uri.as_str())
+            .unwrap()
+            .item
+            .text
+            .clone()
+    };
+
+    handle_did_change_text_document(&state, &change(2, &["TRUE=0\n", "TRUE=1\n"]));
+    assert_eq!(text(), "TRUE=1\n");
+    handle_did_change_text_document(&state, &change(3, &[]));
+    handle_did_change_text_document(&state, &change(1, &["FALSE=0\n"]));
+    assert_eq!(text(), "TRUE=1\n");
 }
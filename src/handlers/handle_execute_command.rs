@@ -0,0 +1,144 @@
+use std::sync::Arc;
+
+use log::{error, info};
+use tower_lsp::{
+    lsp_types::{ExecuteCommandParams, LSPAny, MessageActionItem, MessageType},
+    Client,
+};
+
+use crate::{
+    diagnostics::debounce::DiagnosticsScheduler,
+    index::{get_affected_files, get_drivers, set_active_driver},
+    state::ServerState,
+    utils::get_basename_from_uri,
+};
+
+/// Chooses the driver used as analysis context for an include file.
+///
+/// Arguments: the include file uri, then optionally the driver uri. A `null`
+/// driver goes back to analyzing the include file against all of its
+/// drivers. Without a driver argument the user is asked to pick one.
+pub const SELECT_ACTIVE_DRIVER_COMMAND: &str = "pols.selectActiveDriver";
+
+const ALL_DRIVERS: &str = "All drivers";
+
+pub fn get_commands() -> Vec<String> {
+    vec![SELECT_ACTIVE_DRIVER_COMMAND.to_string()]
+}
+
+pub async fn handle_execute_command(
+    client: &Client,
+    state: &Arc<ServerState>,
+    diagnostics: &Arc<DiagnosticsScheduler>,
+    params: &ExecuteCommandParams,
+) -> Option<LSPAny> {
+    info!("received executeCommand request: {}", params.command);
+    match params.command.as_str() {
+        SELECT_ACTIVE_DRIVER_COMMAND => {
+            select_active_driver(client, state, diagnostics, &params.arguments).await
+        }
+        _ => {
+            error!("Unknown command: {}", params.command);
+            None
+        }
+    }
+}
+
+async fn select_active_driver(
+    client: &Client,
+    state: &Arc<ServerState>,
+    diagnostics: &Arc<DiagnosticsScheduler>,
+    arguments: &[LSPAny],
+) -> Option<LSPAny> {
+    let uri = match arguments.first().and_then(|uri| uri.as_str()) {
+        Some(uri) => uri.to_string(),
+        None => {
+            error!("{} requires a file uri", SELECT_ACTIVE_DRIVER_COMMAND);
+            return None;
+        }
+    };
+
+    let drivers = get_drivers(&state.snapshot(), &uri);
+    if drivers.is_empty() {
+        client
+            .show_message(
+                MessageType::INFO,
+                format!(
+                    "{} is not included by any driver",
+                    get_basename_from_uri(&uri)
+                ),
+            )
+            .await;
+        return None;
+    }
+
+    let driver = match arguments.get(1) {
+        Some(driver) => driver.as_str().map(|driver| driver.to_string()),
+        None => prompt_for_driver(client, &uri, &drivers).await?,
+    };
+    if let Some(driver) = &driver {
+        if !drivers.contains(driver) {
+            client
+                .show_message(
+                    MessageType::WARNING,
+                    format!(
+                        "{} does not include {}",
+                        get_basename_from_uri(driver),
+                        get_basename_from_uri(&uri)
+                    ),
+                )
+                .await;
+            return None;
+        }
+    }
+
+    state.edit(|snapshot| set_active_driver(snapshot, &uri, driver.clone()));
+    let affected_files = get_affected_files(&state.snapshot(), &uri);
+    diagnostics.schedule(client, state, affected_files);
+
+    Some(match driver {
+        Some(driver) => LSPAny::String(driver),
+        None => LSPAny::Null,
+    })
+}
+
+/// Asks the user to pick one of `drivers`. Returns `None` when the prompt is
+/// dismissed and `Some(None)` when all drivers are chosen.
+async fn prompt_for_driver(
+    client: &Client,
+    uri: &str,
+    drivers: &[String],
+) -> Option<Option<String>> {
+    let mut actions: Vec<MessageActionItem> = vec![MessageActionItem {
+        title: ALL_DRIVERS.to_string(),
+        properties: Default::default(),
+    }];
+    actions.extend(drivers.iter().map(|driver| MessageActionItem {
+        title: get_basename_from_uri(driver),
+        properties: Default::default(),
+    }));
+
+    let message = format!(
+        "Choose the driver used to analyze {}",
+        get_basename_from_uri(uri)
+    );
+    let choice = match client
+        .show_message_request(MessageType::INFO, message, Some(actions))
+        .await
+    {
+        Ok(Some(choice)) => choice,
+        Ok(None) => return None,
+        Err(e) => {
+            error!("Error showing message request: {}", e);
+            return None;
+        }
+    };
+
+    if choice.title == ALL_DRIVERS {
+        return Some(None);
+    }
+    drivers
+        .iter()
+        .find(|driver| get_basename_from_uri(driver) == choice.title)
+        .map(|driver| Some(driver.clone()))
+}
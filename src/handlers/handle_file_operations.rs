@@ -0,0 +1,173 @@
+use std::path::Path;
+
+use log::error;
+use tower_lsp::lsp_types::{CreateFilesParams, DeleteFilesParams, RenameFilesParams, Url};
+
+use crate::{
+    index::{
+        file_kind::get_file_kind_from_path,
+        files::{get_workspace_files, is_workspace_file},
+        get_affected_files, index_document, remove_document, set_file_kind, uncache_document,
+    },
+    state::{Document, ServerState, Snapshot},
+    utils::read_document_from_url,
+};
+
+/// Indexes newly created files and returns the files whose diagnostics may
+/// have changed.
+pub async fn handle_did_create_files(
+    state: &ServerState,
+    params: &CreateFilesParams,
+) -> Vec<String> {
+    let mut affected_files: Vec<String> = Vec::new();
+    for file in &params.files {
+        for uri in expand_uri(&state.snapshot(), &file.uri) {
+            add_file(state, &uri).await;
+            extend_unique(
+                &mut affected_files,
+                get_affected_files(&state.snapshot(), &uri),
+            );
+        }
+    }
+    affected_files
+}
+
+pub fn handle_did_delete_files(state: &ServerState, params: &DeleteFilesParams) -> Vec<String> {
+    let mut affected_files: Vec<String> = Vec::new();
+    for file in &params.files {
+        for uri in indexed_files_under(&state.snapshot(), &file.uri) {
+            extend_unique(
+                &mut affected_files,
+                get_affected_files(&state.snapshot(), &uri),
+            );
+            remove_file(state, &uri);
+        }
+    }
+    affected_files
+}
+
+pub async fn handle_did_rename_files(
+    state: &ServerState,
+    params: &RenameFilesParams,
+) -> Vec<String> {
+    let mut affected_files: Vec<String> = Vec::new();
+    for file in &params.files {
+        for uri in indexed_files_under(&state.snapshot(), &file.old_uri) {
+            extend_unique(
+                &mut affected_files,
+                get_affected_files(&state.snapshot(), &uri),
+            );
+            remove_file(state, &uri);
+        }
+        for uri in expand_uri(&state.snapshot(), &file.new_uri) {
+            add_file(state, &uri).await;
+            extend_unique(
+                &mut affected_files,
+                get_affected_files(&state.snapshot(), &uri),
+            );
+        }
+    }
+    affected_files
+}
+
+async fn add_file(state: &ServerState, uri: &str) {
+    let url = match Url::parse(uri) {
+        Ok(url) => url,
+        Err(e) => {
+            error!("Error parsing file url: {}", e);
+            return;
+        }
+    };
+    let path_kind = url
+        .to_file_path()
+        .ok()
+        .and_then(|path| get_file_kind_from_path(&path));
+    if let Some(kind) = path_kind.filter(|kind| !kind.is_poweron()) {
+        state.edit(|snapshot| set_file_kind(snapshot, uri, kind));
+        return;
+    }
+    let encoding = state.snapshot().settings.files.encoding;
+    let document = Document::parsed(read_document_from_url(url, encoding).await);
+    state.edit(|snapshot| index_document(snapshot, document));
+}
+
+fn remove_file(state: &ServerState, uri: &str) {
+    state.edit(|snapshot| remove_document(snapshot, uri));
+    uncache_document(state, uri);
+}
+
+/// A created or renamed uri may be a folder, in which case every file below
+/// it is indexed. Files the workspace filters leave out are not indexed.
+fn expand_uri(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    let path = match Url::parse(uri).map(|url| url.to_file_path()) {
+        Ok(Ok(path)) => path,
+        _ => return Vec::new(),
+    };
+    let settings = &snapshot.settings.files;
+    if path.is_dir() {
+        return get_workspace_files(&path, settings);
+    }
+
+    let root = snapshot
+        .workspace_folders
+        .iter()
+        .find(|folder| path.starts_with(folder))
+        .cloned();
+    let root = root.unwrap_or_else(|| path.parent().unwrap_or(Path::new("/")).to_path_buf());
+    match is_workspace_file(&root, &path, settings) {
+        true => vec![uri.to_string()],
+        false => Vec::new(),
+    }
+}
+
+/// A deleted uri no longer exists on disk, so folders are matched by prefix
+/// against the indexed files. Letter and help files only have a kind, so
+/// they are looked up there as well as in the documents.
+fn indexed_files_under(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    let folder = format!("{}/", uri.trim_end_matches('/'));
+    let mut files = Vec::new();
+    let indexed = snapshot.documents.keys().chain(snapshot.file_kinds.keys());
+    for file in indexed.filter(|file| file.as_str() == uri || file.starts_with(&folder)) {
+        if !files.contains(file) {
+            files.push(file.clone());
+        }
+    }
+    files
+}
+
+fn extend_unique(files: &mut Vec<String>, new_files: Vec<String>) {
+    for file in new_files {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+}
+
+#[test]
+fn test_delete_folder_with_letter_file() {
+    use tower_lsp::lsp_types::FileDelete;
+
+    use crate::{index::file_kind::FileKind, test_utils::document};
+
+    let letter = "file:///specs/letters/NOTICE.LTR";
+    let state = ServerState::new();
+    state.edit(|snapshot| {
+        index_document(snapshot, document("file:///specs/letters/A.PRO", "X=1\n"));
+        index_document(snapshot, document("file:///specs/B.PRO", "Y=1\n"));
+        set_file_kind(snapshot, letter, FileKind::Letter);
+    });
+
+    let params = DeleteFilesParams {
+        files: vec![FileDelete {
+            uri: "file:///specs/letters".to_string(),
+        }],
+    };
+    handle_did_delete_files(&state, &params);
+
+    let snapshot = state.snapshot();
+    assert!(!snapshot.file_kinds.contains_key(letter));
+    assert!(!snapshot
+        .documents
+        .contains_key("file:///specs/letters/A.PRO"));
+    assert!(snapshot.documents.contains_key("file:///specs/B.PRO"));
+}
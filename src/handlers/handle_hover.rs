@@ -1,53 +1,46 @@
 use log::info;
 use tower_lsp::lsp_types::{
-    Hover, HoverContents, HoverParams, MarkupContent, MarkupKind, Position, Range,
+    Hover, HoverContents, HoverParams, MarkupContent, MarkupKind, Position,
 };
-use tree_sitter::Point;
+use tree_sitter::Node;
 
-use crate::{database::account_record_fields::ACCOUNT_RECORD_FIELDS, lsp::CONTEXT};
+use crate::{
+    cancellation::CancellationToken,
+    database::{functions::BUILTIN_FUNCTIONS, record_types::record_fields},
+    diagnostics::{
+        field_values::{builtin_named, check_constant, compared_field, describe_field},
+        literals::Date,
+    },
+    state::Snapshot,
+    text::position_to_point,
+    utils::{get_basename_from_uri, node_range},
+};
 
-pub fn handle_hover(params: &HoverParams) -> Option<Hover> {
+pub fn handle_hover(
+    snapshot: &Snapshot,
+    params: &HoverParams,
+    token: &CancellationToken,
+) -> Option<Hover> {
     info!("received hover request ");
-    let line = params.text_document_position_params.position.line as usize;
-    let col = params.text_document_position_params.position.character as usize;
-    let documents = match CONTEXT.documents.lock() {
-        Ok(documents) => documents.clone(),
-        Err(_) => return None,
-    };
-
-    let document = match documents.get(
-        &params
+    let document = snapshot.document(
+        params
             .text_document_position_params
             .text_document
             .uri
-            .to_string(),
-    ) {
-        Some(document) => document,
-        None => return None,
-    };
-    let trees = match CONTEXT.trees.lock() {
-        Ok(trees) => trees.clone(),
-        Err(_) => return None,
-    };
+            .as_str(),
+    )?;
+    let tree = document.tree()?;
+    let document = &document.item;
 
-    let tree = match trees.get(
-        &params
-            .text_document_position_params
-            .text_document
-            .uri
-            .to_string(),
-    ) {
-        Some(tree) => tree.clone(),
-        None => return None,
-    };
-
-    let p = Point {
-        row: line,
-        column: col,
-    };
+    let p = position_to_point(
+        &document.text,
+        params.text_document_position_params.position,
+    );
 
     let node = tree.root_node().named_descendant_for_point_range(p, p);
-    info!("found node kind  {} ", node?.kind());
+    if let Some(node) = node {
+        info!("found node kind  {} ", node.kind());
+    }
     match node {
         Some(node) => match node.kind() {
             "field_name" => {
@@ -59,61 +52,269 @@ pub fn handle_hover(params: &HoverParams) -> Option<Hover> {
                 let field_name = field_name.trim();
                 info!("field name {} ", field_name);
                 // get parent node
-                let mut parent_node = match node.parent() {
-                    Some(parent_node) => parent_node,
-                    None => return None,
-                };
+                let mut parent_node = node.parent()?;
                 if parent_node.kind() == "field_name" {
-                    parent_node = match parent_node.prev_named_sibling() {
-                        Some(parent_node) => parent_node,
-                        None => return None,
-                    };
+                    parent_node = parent_node.prev_named_sibling()?;
                 };
                 info!("parent node kind {} ", parent_node.kind());
                 if parent_node.kind() == "record_type" {
                     let record_type = match parent_node.utf8_text(document.text.as_bytes()) {
-                        Ok(record_type) => record_type,
+                        Ok(record_type) => record_type.replace(':', ""),
                         Err(_) => return None,
                     };
-                    let record_type = record_type.to_lowercase().replace(":", "");
-                    let record_type = record_type.trim();
-                    match record_type {
-                        "account" => {
-                            let start_pos = Position {
-                                line: node.start_position().row as u32,
-                                character: node.start_position().column as u32,
-                            };
-                            let end_pos = Position {
-                                line: node.end_position().row as u32,
-                                character: node.end_position().column as u32,
-                            };
-
-                            let range = Range {
-                                start: start_pos,
-                                end: end_pos,
-                            };
-
-                            match ACCOUNT_RECORD_FIELDS.get(field_name) {
-                                Some(field) => {
-                                    let hover = Hover {
-                                        contents: HoverContents::Markup(MarkupContent {
-                                            kind: MarkupKind::Markdown,
-                                            value: field.details.to_string(),
-                                        }),
-                                        range: Some(range),
-                                    };
-                                    return Some(hover);
-                                }
-                                None => return None,
-                            };
-                        }
-                        _ => return None,
-                    }
+                    let field = record_fields(&record_type)?.get(field_name)?;
+                    return Some(Hover {
+                        contents: HoverContents::Markup(MarkupContent {
+                            kind: MarkupKind::Markdown,
+                            value: field.details.to_string(),
+                        }),
+                        range: Some(node_range(&node, &document.text)),
+                    });
                 }
-                return None;
+                None
+            }
+            "identifier" => {
+                let name = node.utf8_text(document.text.as_bytes()).ok()?.trim();
+                let is_procedure = match node.parent() {
+                    Some(parent) => {
+                        parent.kind() == "procedure_call" || parent.kind() == "procedure_definition"
+                    }
+                    None => false,
+                };
+                // Builtins the grammar does not know parse as identifiers.
+                let value =
+                    get_symbol_hover(snapshot, token, document.uri.as_str(), name, is_procedure)
+                        .or_else(|| {
+                            let function = BUILTIN_FUNCTIONS.get(name.to_uppercase().as_str())?;
+                            Some(function.documentation())
+                        })?;
+                Some(Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value,
+                    }),
+                    range: Some(node_range(&node, &document.text)),
+                })
+            }
+            "date" => {
+                let value = get_date_hover(node, &document.text)?;
+                Some(Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value,
+                    }),
+                    range: Some(node_range(&node, &document.text)),
+                })
+            }
+            "string_literal" | "number" => {
+                let value = get_literal_hover(node, &document.text)?;
+                Some(Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value,
+                    }),
+                    range: Some(node_range(&node, &document.text)),
+                })
             }
-            _ => return None,
+            // Builtins the grammar parses as keywords or statements of their own.
+            "keyword" | "setwarn" | "clearwarn" => get_function_hover(node, &document.text),
+            _ if node.parent()?.kind() == "poweron_function" => {
+                get_function_hover(node, &document.text)
+            }
+            _ => None,
         },
-        None => return None,
+        None => None,
+    }
+}
+
+/// Describes the builtin whose name `node` starts with, over the name.
+fn get_function_hover(node: Node, text: &str) -> Option<Hover> {
+    let function = builtin_named(node, text)?;
+    let mut range = node_range(&node, text);
+    range.end = Position {
+        line: range.start.line,
+        character: range.start.character + function.name.len() as u32,
+    };
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: function.documentation(),
+        }),
+        range: Some(range),
+    })
+}
+
+/// The date a date literal stands for, in full, and its day of the week.
+fn get_date_hover(node: Node, text: &str) -> Option<String> {
+    let literal = node.utf8_text(text.as_bytes()).ok()?;
+    Some(match Date::parse(literal) {
+        Ok(Some(date)) => format!("{}, a {}", date, date.weekday()),
+        Ok(None) => "No date".to_string(),
+        Err(problem) => problem,
+    })
+}
+
+/// Describes the field a literal is compared with, and why the literal is
+/// not one of its values if it is not.
+fn get_literal_hover(node: Node, text: &str) -> Option<String> {
+    let (record, field) = compared_field(node, text)?;
+    let mut value = format!(
+        "```poweron\n{}:{}\n```\nAccepts {}.",
+        record,
+        field.mnemonic.to_uppercase(),
+        describe_field(field)
+    );
+    if let Some(problem) = check_constant(field, node, text) {
+        value.push_str("\n\n");
+        value.push_str(&problem);
+    }
+    Some(value)
+}
+
+/// Describes every declaration of `name` visible from `uri`. An include file
+/// sees the declarations of the drivers that include it, so the same name may
+/// resolve to more than one declaration.
+fn get_symbol_hover(
+    snapshot: &Snapshot,
+    token: &CancellationToken,
+    uri: &str,
+    name: &str,
+    is_procedure: bool,
+) -> Option<String> {
+    let scope = snapshot.analysis.scope(snapshot, uri, token).ok()?;
+
+    let declarations: Vec<String> = if is_procedure {
+        scope
+            .procedures(name)
+            .iter()
+            .map(|(file, procedure)| {
+                format!(
+                    "```poweron\nPROCEDURE {}\n```\nDefined in {}",
+                    procedure.name,
+                    get_basename_from_uri(file)
+                )
+            })
+            .collect()
+    } else {
+        scope
+            .variables(name)
+            .iter()
+            .map(|(file, variable)| {
+                format!(
+                    "```poweron\n{}={}\n```\nDeclared in {}",
+                    variable.name,
+                    variable.type_description(),
+                    get_basename_from_uri(file)
+                )
+            })
+            .collect()
+    };
+
+    if declarations.is_empty() {
+        return None;
+    }
+    Some(declarations.join("\n\n---\n\n"))
+}
+
+#[test]
+fn test_literal_hover() {
+    let text = "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n IF ACCOUNT:BRANCH=12000 THEN\n  PRINT 1\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let byte = text.find("12000").unwrap();
+    let node = tree
+        .root_node()
+        .named_descendant_for_byte_range(byte, byte)
+        .unwrap();
+    assert_eq!(
+        get_literal_hover(node, text).unwrap(),
+        "```poweron\nACCOUNT:BRANCH\n```\nAccepts a code from 0 to 9999.\n\n\
+         12000 is not a valid code. BRANCH is a code from 0 to 9999."
+    );
+
+    let byte = text.find("1\nEND").unwrap();
+    let node = tree
+        .root_node()
+        .named_descendant_for_byte_range(byte, byte)
+        .unwrap();
+    assert_eq!(get_literal_hover(node, text), None);
+}
+
+#[test]
+fn test_date_hover() {
+    let text = "PRINT TITLE=\"X\"\n X='12/31/23'\n Y='--/--/--'\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let hover = |needle: &str| {
+        let byte = text.find(needle).unwrap();
+        let node = tree
+            .root_node()
+            .named_descendant_for_byte_range(byte, byte)
+            .unwrap();
+        get_date_hover(node, text)
+    };
+    assert_eq!(hover("'12/31/23'").as_deref(), Some("12/31/2023, a Sunday"));
+    assert_eq!(hover("'--/--/--'").as_deref(), Some("No date"));
+}
+
+#[test]
+fn test_function_hover() {
+    let text = "PRINT TITLE=\"X\"\n X=SEGMENT(\"ABC\",1,2)\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let byte = text.find("SEGMENT").unwrap();
+    let node = tree
+        .root_node()
+        .named_descendant_for_byte_range(byte, byte)
+        .unwrap();
+    let hover = get_function_hover(node, text).unwrap();
+    assert_eq!(hover.range.unwrap().end, Position::new(1, 10));
+    match hover.contents {
+        HoverContents::Markup(content) => assert!(content
+            .value
+            .starts_with("```poweron\nSEGMENT(Text,Start,End)\n```\nReturns CHARACTER.")),
+        contents => panic!("unexpected contents {:?}", contents),
+    }
+
+    let text = "PRINT TITLE=\"X\"\n X=AFTERLAST(\"A.B\",\".\")\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let byte = text.find("AFTERLAST").unwrap();
+    let node = tree
+        .root_node()
+        .named_descendant_for_byte_range(byte, byte)
+        .unwrap();
+    let hover = get_function_hover(node, text).unwrap();
+    assert_eq!(hover.range.unwrap().end, Position::new(1, 12));
+}
+
+#[test]
+fn test_field_hover() {
+    use crate::{
+        database::account_record_fields::ACCOUNT_RECORD_FIELDS, test_utils::snapshot_with,
+    };
+    use tower_lsp::lsp_types::{TextDocumentIdentifier, TextDocumentPositionParams, Url};
+
+    let uri = "file:///specs/DRIVER";
+    let text =
+        "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n PRINT ACCOUNT:BRANCH\n PRINT ACCOUNT:COLOR\nEND\n";
+    let snapshot = snapshot_with(&[(uri, text)]);
+    let hover = |line, character| {
+        let params = HoverParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: Url::parse(uri).unwrap(),
+                },
+                position: Position::new(line, character),
+            },
+            work_done_progress_params: Default::default(),
+        };
+        handle_hover(&snapshot, &params, &CancellationToken::new())
+    };
+
+    let branch = hover(2, 17).unwrap();
+    assert_eq!(branch.range.unwrap().start, Position::new(2, 15));
+    match branch.contents {
+        HoverContents::Markup(content) => {
+            assert_eq!(content.value, ACCOUNT_RECORD_FIELDS["branch"].details)
+        }
+        contents => panic!("unexpected contents {:?}", contents),
     }
+    assert!(hover(3, 17).is_none());
 }
@@ -1,6 +1,15 @@
+use std::sync::Arc;
+
 use tower_lsp::lsp_types::*;
 
-pub fn handle_initialize() -> InitializeResult {
+use crate::{settings::get_settings, state::ServerState};
+
+use super::handle_execute_command::get_commands;
+
+pub fn handle_initialize(state: &ServerState, params: &InitializeParams) -> InitializeResult {
+    let settings = get_settings(params.initialization_options.as_ref());
+    state.edit(|snapshot| snapshot.settings = Arc::new(settings));
+
     let file_operation_filter = FileOperationFilter {
         scheme: None,
         pattern: FileOperationPattern {
@@ -23,7 +32,7 @@ pub fn handle_initialize() -> InitializeResult {
 
     InitializeResult {
         capabilities: ServerCapabilities {
-            position_encoding: None,
+            position_encoding: Some(PositionEncodingKind::UTF16),
             text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
             selection_range_provider: None,
             hover_provider: Some(HoverProviderCapability::Simple(true)),
@@ -40,7 +49,11 @@ pub fn handle_initialize() -> InitializeResult {
                     label_details_support: None,
                 }),
             }),
-            signature_help_provider: None,
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
+                retrigger_characters: None,
+                work_done_progress_options: Default::default(),
+            }),
             definition_provider: Some(OneOf::Left(true)),
             type_definition_provider: None,
             implementation_provider: None,
@@ -58,7 +71,10 @@ pub fn handle_initialize() -> InitializeResult {
             color_provider: None,
             folding_range_provider: None,
             declaration_provider: None,
-            execute_command_provider: None,
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: get_commands(),
+                work_done_progress_options: Default::default(),
+            }),
             workspace: Some(WorkspaceServerCapabilities {
                 workspace_folders: Some(WorkspaceFoldersServerCapabilities {
                     supported: Some(true),
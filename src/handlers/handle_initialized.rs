@@ -1,13 +1,51 @@
-use log::error;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use log::{error, info, warn};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tower_lsp::{
-    lsp_types::{MessageType, Url},
+    lsp_types::{
+        notification::Progress, request::WorkDoneProgressCreate, MessageType, NumberOrString,
+        ProgressParams, ProgressParamsValue, Url, WorkDoneProgress, WorkDoneProgressBegin,
+        WorkDoneProgressCreateParams, WorkDoneProgressEnd, WorkDoneProgressReport,
+    },
     Client,
 };
 
-use crate::lsp::CONTEXT;
-use crate::utils::{analyze, get_files_in_dir, read_document_from_url};
+use crate::cancellation::CancellationToken;
+use crate::diagnostics::debounce::DiagnosticsScheduler;
+use crate::index::cache::{get_cache_path, get_file_state, CachedFile, FileState, IndexCache};
+use crate::index::file_kind::{get_file_kind_from_path, FileKind};
+use crate::index::files::get_workspace_files;
+use crate::index::{
+    cache_document, index_document, lock_index_cache, restore_document, save_index_cache,
+    set_file_kind,
+};
+use crate::state::{Document, ServerState, Snapshot};
+use crate::text::Encoding;
+use crate::utils::read_document;
+
+const INDEXING_PROGRESS_TOKEN: &str = "pols/indexing";
+
+/// The outcome of indexing one file on a worker.
+enum IndexedFile {
+    Parsed(Document, Option<FileState>),
+    Restored(Document, CachedFile),
+    /// A file that is not PowerOn source, such as a letter file.
+    Other(String, FileKind),
+    Skipped(String),
+}
 
-pub async fn handle_initialized(client: &Client) {
+pub async fn handle_initialized(
+    client: &Client,
+    state: &Arc<ServerState>,
+    diagnostics: &Arc<DiagnosticsScheduler>,
+) {
     let workspace_folders = match client.workspace_folders().await {
         Ok(folders) => match folders {
             Some(folders) => folders,
@@ -32,6 +70,17 @@ pub async fn handle_initialized(client: &Client) {
         }
     };
 
+    // Files that are unchanged since the last session are restored from the
+    // cache instead of being parsed again.
+    let cache_path = get_cache_path(&workspace_folders);
+    let previous_cache = match &cache_path {
+        Some(path) => IndexCache::load(path.clone()),
+        None => IndexCache::new(None),
+    };
+    *lock_index_cache(state) = IndexCache::new(cache_path);
+
+    let file_settings = state.snapshot().settings.files.clone();
+    let mut folder_paths: Vec<PathBuf> = Vec::new();
     let mut files: Vec<String> = Vec::new();
     for folder in workspace_folders {
         client
@@ -40,59 +89,288 @@ pub async fn handle_initialized(client: &Client) {
                 format!("Adding workspace folder Folder: {}", folder.name),
             )
             .await;
-        let mut files_to_append = match get_files_in_dir(folder.uri.to_string()) {
-            Ok(files) => files,
-            Err(e) => {
-                error!("Error getting files in dir: {}", e);
-                return;
+        let path = match folder.uri.to_file_path() {
+            Ok(path) => path,
+            Err(_) => {
+                error!("Workspace folder {} is not a local folder", folder.uri);
+                continue;
             }
         };
-        files.append(&mut files_to_append);
+        files.append(&mut get_workspace_files(&path, &file_settings));
+        folder_paths.push(path);
     }
+    state.edit(|snapshot| snapshot.workspace_folders = Arc::new(folder_paths));
 
-    for file in files {
-        let url = match Url::parse(&file) {
-            Ok(url) => url,
-            Err(e) => {
-                client
-                    .log_message(MessageType::ERROR, format!("Error parsing file url: {}", e))
-                    .await;
-                return;
-            }
-        };
+    // Requests are served while the workspace is indexed in the background.
+    tokio::spawn(index_workspace(
+        client.clone(),
+        state.clone(),
+        diagnostics.clone(),
+        files,
+        previous_cache,
+    ));
+}
 
-        let text_document = read_document_from_url(url.clone()).await;
-        let mut documents = match CONTEXT.documents.lock() {
-            Ok(documents) => documents,
-            Err(e) => {
-                error!("Error getting documents lock: {}", e);
-                return;
-            }
-        };
-        documents.insert(url.to_string(), text_document.clone());
-
-        match analyze(&text_document) {
-            Ok(tree) => match tree {
-                Some(tree) => {
-                    CONTEXT
-                        .trees
-                        .lock()
-                        .unwrap()
-                        .insert(text_document.uri.to_string(), tree.clone());
-                    tree
-                }
-                None => {
-                    return;
-                }
-            },
+/// Indexes `files` on a pool of blocking workers, reporting progress to the
+/// client, then schedules the diagnostics of every indexed file. Files that
+/// cannot be read or parsed are logged and skipped.
+async fn index_workspace(
+    client: Client,
+    state: Arc<ServerState>,
+    diagnostics: Arc<DiagnosticsScheduler>,
+    files: Vec<String>,
+    previous_cache: IndexCache,
+) {
+    let total = files.len();
+    let progress = begin_progress(&client, total).await;
 
-            Err(_) => {
-                return;
+    let files = Arc::new(files);
+    let previous_cache = Arc::new(previous_cache);
+    let next_file = Arc::new(AtomicUsize::new(0));
+    let encoding = state.snapshot().settings.files.encoding;
+    let (sender, mut receiver) = unbounded_channel::<IndexedFile>();
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(total.max(1));
+    for _ in 0..workers {
+        let files = files.clone();
+        let previous_cache = previous_cache.clone();
+        let next_file = next_file.clone();
+        let sender = sender.clone();
+        let token = state.shutdown.clone();
+        tokio::task::spawn_blocking(move || {
+            index_files(
+                &files,
+                &previous_cache,
+                &next_file,
+                encoding,
+                &sender,
+                &token,
+            )
+        });
+    }
+    drop(sender);
+
+    let mut indexed = 0;
+    let mut skipped = 0;
+    // Letter and help files are read but are not specfiles.
+    let mut others = 0;
+    let mut last_percentage = 0;
+    while let Some(first) = receiver.recv().await {
+        // Whatever the workers finished meanwhile is applied in one edit, so
+        // the workspace state is not copied once per file.
+        let mut batch = vec![first];
+        while let Ok(indexed_file) = receiver.try_recv() {
+            batch.push(indexed_file);
+        }
+        indexed += batch.len();
+        others += batch
+            .iter()
+            .filter(|indexed_file| matches!(indexed_file, IndexedFile::Other(..)))
+            .count();
+
+        let mut errors: Vec<String> = Vec::new();
+        let mut to_cache: Vec<(String, FileState)> = Vec::new();
+        state.edit(|snapshot| {
+            for indexed_file in batch {
+                apply_indexed_file(snapshot, indexed_file, &mut errors, &mut to_cache);
             }
-        };
+        });
+        let snapshot = state.snapshot();
+        for (uri, file_state) in to_cache {
+            cache_document(&state, &snapshot, &uri, file_state);
+        }
+
+        skipped += errors.len();
+        for e in errors {
+            warn!("Skipped {}", e);
+            client
+                .log_message(MessageType::WARNING, format!("Skipped {}", e))
+                .await;
+        }
+        let percentage = (indexed * 100 / total) as u32;
+        if progress && (percentage != last_percentage || indexed == total) {
+            last_percentage = percentage;
+            report_progress(
+                &client,
+                WorkDoneProgress::Report(WorkDoneProgressReport {
+                    cancellable: Some(false),
+                    message: Some(format!("Indexing {}/{} specfiles", indexed, total)),
+                    percentage: Some(percentage),
+                }),
+            )
+            .await;
+        }
+    }
+
+    // The shutdown already saved the index cache.
+    if state.shutdown.is_cancelled() {
+        let message = format!(
+            "Indexing stopped by shutdown after {}/{} specfiles",
+            indexed, total
+        );
+        info!("{}", message);
+        if progress {
+            end_progress(&client, message).await;
+        }
+        return;
     }
 
+    save_index_cache(&state);
+    let message = format!(
+        "Indexed {} specfiles, skipped {}",
+        total - skipped - others,
+        skipped
+    );
+    info!("{}", message);
+    if progress {
+        end_progress(&client, message).await;
+    }
+
+    // Computed on a blocking thread through the debounced scheduler. An edit
+    // made meanwhile cancels the run and reschedules these files with its own.
+    let files = state.snapshot().include_graph.files();
+    diagnostics.schedule(&client, &state, files);
+
     client
         .log_message(MessageType::INFO, "PowerOn LSP initialized".to_string())
         .await;
 }
+
+/// Adds the outcome of indexing a file to `snapshot`, collecting errors and
+/// the files whose analysis should be written to the index cache.
+fn apply_indexed_file(
+    snapshot: &mut Snapshot,
+    indexed_file: IndexedFile,
+    errors: &mut Vec<String>,
+    to_cache: &mut Vec<(String, FileState)>,
+) {
+    let (document, cached_file, file_state) = match indexed_file {
+        IndexedFile::Skipped(e) => {
+            errors.push(e);
+            return;
+        }
+        IndexedFile::Other(uri, kind) => {
+            set_file_kind(snapshot, &uri, kind);
+            return;
+        }
+        IndexedFile::Parsed(document, file_state) => (document, None, file_state),
+        IndexedFile::Restored(document, cached_file) => {
+            let file_state = Some(cached_file.state);
+            (document, Some(cached_file), file_state)
+        }
+    };
+
+    // A document the client changed while indexing is newer than the file
+    // on disk, and has already been indexed.
+    let uri = document.item.uri.to_string();
+    if snapshot.documents.contains_key(&uri) {
+        return;
+    }
+    match cached_file {
+        Some(cached_file) => restore_document(snapshot, document, &cached_file),
+        None => index_document(snapshot, document),
+    }
+    if let Some(file_state) = file_state {
+        to_cache.push((uri, file_state));
+    }
+}
+
+/// A worker: takes files off the shared list until none are left or the
+/// server shuts down.
+fn index_files(
+    files: &[String],
+    previous_cache: &IndexCache,
+    next_file: &AtomicUsize,
+    encoding: Encoding,
+    sender: &UnboundedSender<IndexedFile>,
+    token: &CancellationToken,
+) {
+    while !token.is_cancelled() {
+        let file = match files.get(next_file.fetch_add(1, Ordering::Relaxed)) {
+            Some(file) => file,
+            None => return,
+        };
+        let indexed_file = match index_file(file, previous_cache, encoding) {
+            Ok(indexed_file) => indexed_file,
+            Err(e) => IndexedFile::Skipped(e),
+        };
+        if sender.send(indexed_file).is_err() {
+            return;
+        }
+    }
+}
+
+fn index_file(
+    file: &str,
+    previous_cache: &IndexCache,
+    encoding: Encoding,
+) -> Result<IndexedFile, String> {
+    let url = Url::parse(file).map_err(|e| format!("{}: invalid url: {}", file, e))?;
+    let path_kind = url
+        .to_file_path()
+        .ok()
+        .and_then(|path| get_file_kind_from_path(&path));
+    if let Some(kind) = path_kind.filter(|kind| !kind.is_poweron()) {
+        return Ok(IndexedFile::Other(url.to_string(), kind));
+    }
+    let text_document = read_document(&url, encoding).map_err(|e| format!("{}: {}", file, e))?;
+
+    let file_state = get_file_state(&url, &text_document.text);
+    if let Some(cached) = file_state.and_then(|state| previous_cache.lookup(url.as_str(), &state)) {
+        return Ok(IndexedFile::Restored(
+            Document::new(text_document),
+            cached.clone(),
+        ));
+    }
+    // Parsed here, on the worker, rather than while the state is edited.
+    let document = Document::parsed(text_document);
+    if document.tree().is_none() {
+        return Err(format!("{}: parse failed", file));
+    }
+    Ok(IndexedFile::Parsed(document, file_state))
+}
+
+/// Starts the indexing progress. Returns false when the client does not
+/// accept work done progress, in which case no progress is reported.
+async fn begin_progress(client: &Client, total: usize) -> bool {
+    let token = NumberOrString::String(INDEXING_PROGRESS_TOKEN.to_string());
+    if let Err(e) = client
+        .send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams { token })
+        .await
+    {
+        info!("Not reporting indexing progress: {}", e);
+        return false;
+    }
+    report_progress(
+        client,
+        WorkDoneProgress::Begin(WorkDoneProgressBegin {
+            title: "Indexing".to_string(),
+            cancellable: Some(false),
+            message: Some(format!("Indexing 0/{} specfiles", total)),
+            percentage: Some(0),
+        }),
+    )
+    .await;
+    true
+}
+
+async fn end_progress(client: &Client, message: String) {
+    report_progress(
+        client,
+        WorkDoneProgress::End(WorkDoneProgressEnd {
+            message: Some(message),
+        }),
+    )
+    .await;
+}
+
+async fn report_progress(client: &Client, progress: WorkDoneProgress) {
+    client
+        .send_notification::<Progress>(ProgressParams {
+            token: NumberOrString::String(INDEXING_PROGRESS_TOKEN.to_string()),
+            value: ProgressParamsValue::WorkDone(progress),
+        })
+        .await;
+}
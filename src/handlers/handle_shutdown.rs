@@ -0,0 +1,13 @@
+use log::info;
+
+use crate::{index::save_index_cache, state::ServerState};
+
+/// Stops the work still running and saves what should outlive the server.
+/// Requests in flight see their tokens cancelled and answer right away; the
+/// server keeps running until the client sends `exit`.
+pub fn handle_shutdown(state: &ServerState) {
+    info!("received shutdown request");
+    state.shutdown.cancel();
+    save_index_cache(state);
+    log::logger().flush();
+}
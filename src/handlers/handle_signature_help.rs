@@ -0,0 +1,85 @@
+use log::info;
+use tower_lsp::lsp_types::{
+    Documentation, MarkupContent, MarkupKind, ParameterInformation, ParameterLabel, SignatureHelp,
+    SignatureHelpParams, SignatureInformation,
+};
+
+use crate::{
+    completions::context::call_at, database::functions::BUILTIN_FUNCTIONS, state::Snapshot,
+    text::position_to_point,
+};
+
+/// The signature of the builtin whose argument list the cursor is in, with
+/// the argument at the cursor active.
+pub fn handle_signature_help(
+    snapshot: &Snapshot,
+    params: &SignatureHelpParams,
+) -> Option<SignatureHelp> {
+    info!("received signatureHelp request");
+    let position = &params.text_document_position_params;
+    let document = snapshot.document(position.text_document.uri.as_str())?;
+    let tree = document.tree()?;
+    let text = &document.item.text;
+    let point = position_to_point(text, position.position);
+    let (name, argument) = call_at(tree, text, point)?;
+    let function = BUILTIN_FUNCTIONS.get(name.as_str())?;
+
+    let parameters = function
+        .parameters
+        .iter()
+        .map(|parameter| ParameterInformation {
+            label: ParameterLabel::Simple(parameter.name.to_string()),
+            documentation: parameter
+                .data_type
+                .map(|data_type| Documentation::String(data_type.as_str().to_string())),
+        })
+        .collect();
+    Some(SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label: function.signature(),
+            documentation: Some(Documentation::MarkupContent(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: function.description.to_string(),
+            })),
+            parameters: Some(parameters),
+            active_parameter: None,
+        }],
+        active_signature: Some(0),
+        active_parameter: (argument < function.parameters.len()).then_some(argument as u32),
+    })
+}
+
+#[test]
+fn test_signature_help() {
+    use crate::test_utils::snapshot_with;
+    use tower_lsp::lsp_types::{Position, TextDocumentIdentifier, TextDocumentPositionParams, Url};
+
+    let uri = Url::parse("file:///specs/DRIVER").unwrap();
+    let text = "PRINT TITLE=\"X\"\n X=SEGMENT(\"ABC\",1,\n X=AFTERLAST(\"A.B\",\nEND\n";
+    let snapshot = snapshot_with(&[(uri.as_str(), text)]);
+    let help = |line, character| {
+        handle_signature_help(
+            &snapshot,
+            &SignatureHelpParams {
+                context: None,
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier { uri: uri.clone() },
+                    position: Position::new(line, character),
+                },
+                work_done_progress_params: Default::default(),
+            },
+        )
+    };
+
+    let signature_help = help(1, 20).unwrap();
+    assert_eq!(
+        signature_help.signatures[0].label,
+        "SEGMENT(Text,Start,End)"
+    );
+    assert_eq!(signature_help.active_parameter, Some(2));
+    assert_eq!(help(1, 11).unwrap().active_parameter, Some(0));
+    assert!(help(1, 2).is_none());
+    let after_last = help(2, 19).unwrap();
+    assert_eq!(after_last.signatures[0].label, "AFTERLAST(Text,Search)");
+    assert_eq!(after_last.active_parameter, Some(1));
+}
@@ -1,7 +1,12 @@
 pub mod handle_completion;
+pub mod handle_completion_resolve;
 pub mod handle_definition;
 pub mod handle_did_change_text_document;
 pub mod handle_document_symbol;
+pub mod handle_execute_command;
+pub mod handle_file_operations;
 pub mod handle_hover;
 pub mod handle_initialize;
 pub mod handle_initialized;
+pub mod handle_shutdown;
+pub mod handle_signature_help;
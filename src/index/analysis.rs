@@ -0,0 +1,322 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tower_lsp::lsp_types::Diagnostic;
+
+use crate::{
+    cancellation::{Cancellable, CancellationToken},
+    state::Snapshot,
+    sync::lock_or_reset,
+};
+
+use super::{
+    get_analysis_files,
+    record_scope::{get_procedure_records, ProcedureRecords},
+    symbols::{ProcedureSymbol, VariableSymbol},
+};
+
+/// Memoized analysis results shared by every snapshot of a server.
+///
+/// Each result records the revision of the include graph and of every file
+/// it was computed from. A result is reused for as long as none of them
+/// changed in the snapshot asking for it, so an edit only invalidates the
+/// results that read the edited file.
+#[derive(Debug, Default)]
+pub struct AnalysisDatabase {
+    scopes: Mutex<HashMap<String, Memo<Arc<Scope>>>>,
+    diagnostics: Mutex<HashMap<String, Memo<Arc<Vec<Diagnostic>>>>>,
+    procedure_records: Mutex<HashMap<String, Memo<Arc<ProcedureRecords>>>>,
+    field_uses: Mutex<HashMap<String, Memo<Arc<FieldUses>>>>,
+}
+
+/// How many times the workspace reads or writes each field, keyed by
+/// `field_reference_key`.
+pub type FieldUses = HashMap<String, u32>;
+
+/// The key of results computed from every file of the workspace.
+const WORKSPACE: &str = "";
+
+#[derive(Debug)]
+struct Memo<T> {
+    value: T,
+    graph_revision: u64,
+    dependencies: Vec<(String, u64)>,
+}
+
+impl<T> Memo<T> {
+    fn is_valid(&self, snapshot: &Snapshot) -> bool {
+        self.graph_revision == snapshot.graph_revision
+            && self
+                .dependencies
+                .iter()
+                .all(|(uri, revision)| snapshot.file_revision(uri) == *revision)
+    }
+}
+
+/// Every declaration visible from a file: its own, its includes' and, for an
+/// include file, those of the drivers it is analyzed with. Names are matched
+/// case-insensitively, and declarations are kept in analysis order.
+#[derive(Debug, Default)]
+pub struct Scope {
+    pub files: Vec<String>,
+    variables: HashMap<String, Vec<(String, VariableSymbol)>>,
+    procedures: HashMap<String, Vec<(String, ProcedureSymbol)>>,
+}
+
+impl Scope {
+    /// The declarations of the variable `name`, with the file of each.
+    pub fn variables(&self, name: &str) -> &[(String, VariableSymbol)] {
+        match self.variables.get(&name.to_uppercase()) {
+            Some(variables) => variables,
+            None => &[],
+        }
+    }
+
+    pub fn procedures(&self, name: &str) -> &[(String, ProcedureSymbol)] {
+        match self.procedures.get(&name.to_uppercase()) {
+            Some(procedures) => procedures,
+            None => &[],
+        }
+    }
+
+    /// The declaration that decides the type of `name`: the first one.
+    pub fn variable(&self, name: &str) -> Option<&VariableSymbol> {
+        self.variables(name).first().map(|(_, variable)| variable)
+    }
+
+    pub fn all_variables(&self) -> impl Iterator<Item = &(String, VariableSymbol)> {
+        self.variables.values().flatten()
+    }
+
+    pub fn all_procedures(&self) -> impl Iterator<Item = &(String, ProcedureSymbol)> {
+        self.procedures.values().flatten()
+    }
+}
+
+impl AnalysisDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The scope of `uri`, computed from its analysis files.
+    pub fn scope(
+        &self,
+        snapshot: &Snapshot,
+        uri: &str,
+        token: &CancellationToken,
+    ) -> Cancellable<Arc<Scope>> {
+        memoize(&self.scopes, snapshot, uri, || {
+            let scope = Arc::new(compute_scope(snapshot, uri, token)?);
+            let files = scope.files.clone();
+            Ok((scope, files))
+        })
+    }
+
+    /// The diagnostics of `uri`, computed by `compute` from the files it
+    /// returns alongside them.
+    pub fn diagnostics(
+        &self,
+        snapshot: &Snapshot,
+        uri: &str,
+        compute: impl FnOnce() -> Cancellable<(Vec<Diagnostic>, Vec<String>)>,
+    ) -> Cancellable<Arc<Vec<Diagnostic>>> {
+        memoize(&self.diagnostics, snapshot, uri, || {
+            let (diagnostics, files) = compute()?;
+            Ok((Arc::new(diagnostics), files))
+        })
+    }
+
+    /// The records current in each procedure `driver` calls, computed from
+    /// the driver and the files it includes.
+    pub fn procedure_records(
+        &self,
+        snapshot: &Snapshot,
+        driver: &str,
+        token: &CancellationToken,
+    ) -> Cancellable<Arc<ProcedureRecords>> {
+        memoize(&self.procedure_records, snapshot, driver, || {
+            let procedures = Arc::new(get_procedure_records(snapshot, driver, token)?);
+            let files = std::iter::once(driver.to_string())
+                .chain(snapshot.include_graph.include_closure(driver))
+                .collect();
+            Ok((procedures, files))
+        })
+    }
+
+    /// The field uses of every file in the workspace. Adding or removing a
+    /// file changes the include graph, so the files it was computed from
+    /// are enough to tell when it is stale.
+    pub fn field_uses(
+        &self,
+        snapshot: &Snapshot,
+        token: &CancellationToken,
+    ) -> Cancellable<Arc<FieldUses>> {
+        memoize(&self.field_uses, snapshot, WORKSPACE, || {
+            let mut uses = FieldUses::new();
+            for file_symbols in snapshot.symbols.values() {
+                token.check()?;
+                for (field, count) in &file_symbols.field_references {
+                    *uses.entry(field.clone()).or_default() += count;
+                }
+            }
+            let files = snapshot.symbols.keys().cloned().collect();
+            Ok((Arc::new(uses), files))
+        })
+    }
+
+    /// Drops every result for `uri`, for instance once it is deleted.
+    pub fn forget(&self, uri: &str) {
+        lock_table(&self.scopes).remove(uri);
+        lock_table(&self.diagnostics).remove(uri);
+        lock_table(&self.procedure_records).remove(uri);
+    }
+}
+
+/// Returns the memoized value for `key` when it is still valid in
+/// `snapshot`, otherwise computes and stores it. The lock is not held while
+/// computing, so a slow computation never holds up other lookups. A
+/// cancelled computation stores nothing.
+fn memoize<T: Clone>(
+    table: &Mutex<HashMap<String, Memo<T>>>,
+    snapshot: &Snapshot,
+    key: &str,
+    compute: impl FnOnce() -> Cancellable<(T, Vec<String>)>,
+) -> Cancellable<T> {
+    if let Some(memo) = lock_table(table)
+        .get(key)
+        .filter(|memo| memo.is_valid(snapshot))
+    {
+        return Ok(memo.value.clone());
+    }
+
+    let (value, files) = compute()?;
+    let memo = Memo {
+        value: value.clone(),
+        graph_revision: snapshot.graph_revision,
+        dependencies: files
+            .into_iter()
+            .map(|uri| {
+                let revision = snapshot.file_revision(&uri);
+                (uri, revision)
+            })
+            .collect(),
+    };
+    lock_table(table).insert(key.to_string(), memo);
+    Ok(value)
+}
+
+/// Locks a memo table. The table is emptied when a panic poisoned it, and
+/// its results are computed again as they are asked for.
+fn lock_table<T>(
+    table: &Mutex<HashMap<String, Memo<T>>>,
+) -> MutexGuard<'_, HashMap<String, Memo<T>>> {
+    lock_or_reset(table, |table| table.clear())
+}
+
+fn compute_scope(snapshot: &Snapshot, uri: &str, token: &CancellationToken) -> Cancellable<Scope> {
+    let mut scope = Scope {
+        files: get_analysis_files(snapshot, uri),
+        ..Scope::default()
+    };
+    for file in &scope.files {
+        token.check()?;
+        let file_symbols = match snapshot.symbols.get(file) {
+            Some(file_symbols) => file_symbols,
+            None => continue,
+        };
+        for variable in &file_symbols.variables {
+            scope
+                .variables
+                .entry(variable.name.to_uppercase())
+                .or_default()
+                .push((file.clone(), variable.clone()));
+        }
+        for procedure in &file_symbols.procedures {
+            scope
+                .procedures
+                .entry(procedure.name.to_uppercase())
+                .or_default()
+                .push((file.clone(), procedure.clone()));
+        }
+    }
+    Ok(scope)
+}
+
+#[test]
+fn test_scope_is_invalidated_by_its_files_only() {
+    use crate::{state::ServerState, test_utils::document};
+
+    use super::index_document;
+
+    let driver = "file:///specs/DRIVER";
+    let state = ServerState::new();
+    state.edit(|snapshot| {
+        index_document(
+            snapshot,
+            document(
+                driver,
+                "TARGET=ACCOUNT\n#INCLUDE \"A.DEF\"\nPRINT TITLE=\"X\"\nEND\n",
+            ),
+        );
+        index_document(snapshot, document("file:///specs/A.DEF", "TRUE=1\n"));
+        index_document(snapshot, document("file:///specs/B.DEF", "FALSE=0\n"));
+    });
+
+    let token = CancellationToken::new();
+    let snapshot = state.snapshot();
+    let scope = snapshot.analysis.scope(&snapshot, driver, &token).unwrap();
+    assert_eq!(scope.variables("true").len(), 1);
+    assert!(scope.variables("FALSE").is_empty());
+
+    state.edit(|snapshot| index_document(snapshot, document("file:///specs/B.DEF", "FALSE=1\n")));
+    let snapshot = state.snapshot();
+    assert!(Arc::ptr_eq(
+        &scope,
+        &snapshot.analysis.scope(&snapshot, driver, &token).unwrap()
+    ));
+
+    state.edit(|snapshot| {
+        index_document(snapshot, document("file:///specs/A.DEF", "TRUE=1\nX=2\n"))
+    });
+    let snapshot = state.snapshot();
+    let scope = snapshot.analysis.scope(&snapshot, driver, &token).unwrap();
+    assert_eq!(scope.variables("X").len(), 1);
+
+    token.cancel();
+    state.edit(|snapshot| index_document(snapshot, document("file:///specs/A.DEF", "Y=3\n")));
+    let snapshot = state.snapshot();
+    assert!(snapshot.analysis.scope(&snapshot, driver, &token).is_err());
+}
+
+#[test]
+fn test_field_uses_are_counted_across_files() {
+    use crate::{state::ServerState, test_utils::document};
+
+    use super::{index_document, symbols::field_reference_key};
+
+    let driver = "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n PRINT ACCOUNT:OPENDATE\nEND\n";
+    let procedure = "PROCEDURE SHOWOPENDATE\n PRINT ACCOUNT:OPENDATE\nEND\n";
+    let state = ServerState::new();
+    state.edit(|snapshot| {
+        index_document(snapshot, document("file:///specs/DRIVER", driver));
+        index_document(snapshot, document("file:///specs/A.PRO", procedure));
+    });
+
+    let token = CancellationToken::new();
+    let key = field_reference_key("ACCOUNT", "opendate");
+    let snapshot = state.snapshot();
+    let uses = snapshot.analysis.field_uses(&snapshot, &token).unwrap();
+    assert_eq!(uses.get(&key), Some(&2));
+
+    state.edit(|snapshot| index_document(snapshot, document("file:///specs/A.PRO", "X=1\n")));
+    let snapshot = state.snapshot();
+    let uses = snapshot.analysis.field_uses(&snapshot, &token).unwrap();
+    assert_eq!(uses.get(&key), Some(&1));
+
+    token.cancel();
+    state.edit(|snapshot| index_document(snapshot, document("file:///specs/B.PRO", procedure)));
+    let snapshot = state.snapshot();
+    assert!(snapshot.analysis.field_uses(&snapshot, &token).is_err());
+}
@@ -0,0 +1,247 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use tower_lsp::lsp_types::{Url, WorkspaceFolder};
+
+use crate::database::record_types::{parent_record, record_fields, RECORD_TYPES};
+
+use super::{file_kind::FileKind, include_graph::IncludeStatement, symbols::FileSymbols};
+
+/// Bump whenever the layout of `IndexCache` or the meaning of the cached
+/// analysis changes.
+const CACHE_FORMAT_VERSION: u32 = 3;
+
+/// What was true about a file on disk when it was indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileState {
+    /// Modification time in milliseconds since the unix epoch.
+    pub modified: u64,
+    pub size: u64,
+    pub hash: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFile {
+    pub state: FileState,
+    pub kind: FileKind,
+    pub includes: Vec<IncludeStatement>,
+    pub symbols: FileSymbols,
+}
+
+/// The serialized workspace index. Each entry holds the analysis of a file
+/// as it was on disk, so a restart only reparses the files that changed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IndexCache {
+    version: String,
+    files: HashMap<String, CachedFile>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl IndexCache {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            version: get_cache_version(),
+            files: HashMap::new(),
+            path,
+        }
+    }
+
+    /// Loads the cache at `path`. A missing, unreadable or outdated cache
+    /// gives an empty one that will be written back to the same place.
+    pub fn load(path: PathBuf) -> Self {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::new(Some(path)),
+        };
+        let mut cache: IndexCache = match serde_json::from_str(&contents) {
+            Ok(cache) => cache,
+            Err(e) => {
+                error!("Error reading index cache {}: {}", path.display(), e);
+                return Self::new(Some(path));
+            }
+        };
+        if cache.version != get_cache_version() {
+            info!("Index cache {} is outdated, rebuilding", path.display());
+            return Self::new(Some(path));
+        }
+        cache.path = Some(path);
+        cache
+    }
+
+    pub fn save(&self) {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return,
+        };
+        if let Some(dir) = path.parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                error!("Error creating cache directory {}: {}", dir.display(), e);
+                return;
+            }
+        }
+        let contents = match serde_json::to_string(self) {
+            Ok(contents) => contents,
+            Err(e) => {
+                error!("Error serializing index cache: {}", e);
+                return;
+            }
+        };
+        // Write next to the cache and rename, so a crash never leaves a
+        // half-written cache behind.
+        let tmp_path = path.with_extension("tmp");
+        let result = fs::write(&tmp_path, contents).and_then(|_| fs::rename(&tmp_path, path));
+        match result {
+            Ok(_) => info!("Wrote index cache {}", path.display()),
+            Err(e) => error!("Error writing index cache {}: {}", path.display(), e),
+        }
+    }
+
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// The cached analysis of `uri`, if the file has not changed since. The
+    /// contents decide, so a file that was only touched is still reused.
+    pub fn lookup(&self, uri: &str, state: &FileState) -> Option<&CachedFile> {
+        let cached = self.files.get(uri)?;
+        match cached.state.size == state.size && cached.state.hash == state.hash {
+            true => Some(cached),
+            false => None,
+        }
+    }
+
+    pub fn insert(&mut self, uri: &str, cached: CachedFile) {
+        self.files.insert(uri.to_string(), cached);
+    }
+
+    pub fn remove(&mut self, uri: &str) {
+        self.files.remove(uri);
+    }
+}
+
+pub fn get_file_state(url: &Url, text: &str) -> Option<FileState> {
+    let path = url.to_file_path().ok()?;
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as u64;
+    Some(FileState {
+        modified,
+        size: metadata.len(),
+        hash: hash_bytes(text.as_bytes()),
+    })
+}
+
+/// The cache file for a set of workspace folders. The cache directory is
+/// `$POLS_CACHE_DIR` when set, otherwise the platform cache directory.
+pub fn get_cache_path(workspace_folders: &[WorkspaceFolder]) -> Option<PathBuf> {
+    let cache_dir = match env::var_os("POLS_CACHE_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => get_default_cache_dir()?,
+    };
+    let mut folders: Vec<&str> = workspace_folders.iter().map(|f| f.uri.as_str()).collect();
+    folders.sort();
+    let workspace_hash = hash_bytes(folders.join("\n").as_bytes());
+    Some(cache_dir.join(format!("index-{:016x}.json", workspace_hash)))
+}
+
+fn get_default_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME") {
+        return Some(Path::new(&dir).join("pols"));
+    }
+    if let Some(dir) = env::var_os("LOCALAPPDATA") {
+        return Some(Path::new(&dir).join("pols"));
+    }
+    let home = env::var_os("HOME")?;
+    Some(Path::new(&home).join(".cache").join("pols"))
+}
+
+/// Identifies everything the cached analysis depends on besides the files
+/// themselves: the cache layout, the server, the grammar, and the record
+/// catalog, as cached field references are keyed by record and field.
+fn get_cache_version() -> String {
+    let mut catalog: Vec<String> = Vec::new();
+    for record in RECORD_TYPES {
+        catalog.push(format!("{}<{:?}", record, parent_record(record)));
+        let fields = record_fields(record)
+            .into_iter()
+            .flat_map(|fields| fields.values());
+        for field in fields {
+            catalog.push(format!(
+                "{}:{}:{:?}:{:?}",
+                record, field.mnemonic, field.data_type, field.length
+            ));
+        }
+    }
+    catalog.sort();
+    format!(
+        "{}-{}-{:016x}-{:016x}",
+        CACHE_FORMAT_VERSION,
+        env!("CARGO_PKG_VERSION"),
+        hash_bytes(tree_sitter_poweron::NODE_TYPES.as_bytes()),
+        hash_bytes(catalog.join("\n").as_bytes())
+    )
+}
+
+/// FNV-1a. The std hashers are not guaranteed to be stable between
+/// releases, and these hashes are persisted.
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[test]
+fn test_index_cache_round_trip() {
+    let dir = env::temp_dir().join(format!("pols-cache-test-{}", std::process::id()));
+    let path = dir.join("index.json");
+    let state = FileState {
+        modified: 1,
+        size: 6,
+        hash: hash_bytes(b"TRUE=1"),
+    };
+
+    let mut cache = IndexCache::new(Some(path.clone()));
+    cache.insert(
+        "file:///specs/A.DEF",
+        CachedFile {
+            state,
+            kind: FileKind::Def,
+            includes: Vec::new(),
+            symbols: FileSymbols::default(),
+        },
+    );
+    cache.save();
+
+    let cache = IndexCache::load(path.clone());
+    assert!(cache.lookup("file:///specs/A.DEF", &state).is_some());
+    let touched = FileState {
+        modified: 2,
+        ..state
+    };
+    assert!(cache.lookup("file:///specs/A.DEF", &touched).is_some());
+    let edited = FileState {
+        modified: 2,
+        hash: hash_bytes(b"TRUE=2"),
+        ..state
+    };
+    assert!(cache.lookup("file:///specs/A.DEF", &edited).is_none());
+
+    fs::write(&path, r#"{"version":"0","files":{}}"#).unwrap();
+    let cache = IndexCache::load(path);
+    assert!(cache.lookup("file:///specs/A.DEF", &state).is_none());
+    fs::remove_dir_all(dir).unwrap();
+}
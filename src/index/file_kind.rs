@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tree_sitter::Tree;
+
+/// What a workspace file is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileKind {
+    /// A specfile that is run: it has divisions such as TARGET or PRINT.
+    Driver,
+    /// An include file of variable declarations.
+    Def,
+    /// An include file of procedures.
+    Pro,
+    /// A letter file, merged into letters rather than compiled.
+    Letter,
+    /// A help file.
+    Help,
+}
+
+impl FileKind {
+    /// Letter and help files are plain text, not PowerOn source.
+    pub fn is_poweron(&self) -> bool {
+        !matches!(self, FileKind::Letter | FileKind::Help)
+    }
+}
+
+const DRIVER_DIVISIONS: &[&str] = &[
+    "target_division",
+    "define_division",
+    "setup_division",
+    "select_division",
+    "sort_division",
+    "print_division",
+    "letter_division",
+    "total_division",
+];
+
+/// The kind of a file as told by its extension or the folder it is in, when
+/// they say anything. Specfiles usually have no extension, so drivers are only
+/// recognized by their contents.
+pub fn get_file_kind_from_path(path: &Path) -> Option<FileKind> {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_uppercase());
+    match extension.as_deref() {
+        Some("DEF") => return Some(FileKind::Def),
+        Some("PRO") => return Some(FileKind::Pro),
+        Some("LTR") | Some("LETTER") => return Some(FileKind::Letter),
+        Some("HLP") | Some("HELP") => return Some(FileKind::Help),
+        _ => {}
+    }
+    let folder = path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .map(|folder| folder.to_string_lossy().to_uppercase());
+    match folder.as_deref() {
+        Some("LETTERSPECS") => Some(FileKind::Letter),
+        Some("HELPFILES") => Some(FileKind::Help),
+        _ => None,
+    }
+}
+
+/// The kind of a PowerOn file as told by its syntax tree: any division makes
+/// it a driver, otherwise it is a PRO file when it defines procedures and a
+/// DEF file when it does not.
+pub fn get_file_kind_from_tree(tree: &Tree) -> FileKind {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let mut has_procedures = false;
+    for child in root.children(&mut cursor) {
+        if DRIVER_DIVISIONS.contains(&child.kind()) {
+            return FileKind::Driver;
+        }
+        if child.kind() == "procedure_definition" {
+            has_procedures = true;
+        }
+    }
+    match has_procedures {
+        true => FileKind::Pro,
+        false => FileKind::Def,
+    }
+}
+
+/// The kind of a file from its path, or from its syntax tree when the path
+/// does not say. A file that could not be parsed and whose path does not say
+/// has no kind, rather than being mistaken for a DEF file.
+pub fn classify_file(path: Option<&Path>, tree: Option<&Tree>) -> Option<FileKind> {
+    path.and_then(get_file_kind_from_path)
+        .or_else(|| tree.map(get_file_kind_from_tree))
+}
+
+#[test]
+fn test_classify_file() {
+    use crate::parser::get_parser;
+
+    let mut parser = get_parser();
+    let mut kind = |path: &str, source: &str| {
+        let tree = parser.parse(source, None).unwrap();
+        classify_file(Some(Path::new(path)), Some(&tree)).unwrap()
+    };
+    assert_eq!(
+        kind("/specs/REPORT", "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\nEND\n"),
+        FileKind::Driver
+    );
+    assert_eq!(kind("/specs/COMMON", "TRUE=1\nFALSE=0\n"), FileKind::Def);
+    assert_eq!(
+        kind(
+            "/specs/HEADER",
+            "PROCEDURE PRINTHEADER\n PRINT \"HI\"\nEND\n"
+        ),
+        FileKind::Pro
+    );
+    assert_eq!(kind("/specs/COMMON.PRO", "TRUE=1\n"), FileKind::Pro);
+    assert_eq!(kind("/LETTERSPECS/NOTICE", "Dear member"), FileKind::Letter);
+    assert_eq!(kind("/specs/TELLER.HLP", ""), FileKind::Help);
+    assert_eq!(
+        classify_file(Some(Path::new("/specs/COMMON.DEF")), None),
+        Some(FileKind::Def)
+    );
+    assert_eq!(classify_file(Some(Path::new("/specs/REPORT")), None), None);
+}
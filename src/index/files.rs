@@ -0,0 +1,168 @@
+use std::{
+    fs::File,
+    io::Read,
+    path::{Component, Path},
+};
+
+use ignore::{
+    overrides::{Override, OverrideBuilder},
+    WalkBuilder,
+};
+use log::{error, info};
+use tower_lsp::lsp_types::Url;
+
+use crate::settings::FileSettings;
+
+/// How much of a file is read to tell whether it is binary.
+const BINARY_CHECK_LENGTH: usize = 8 * 1024;
+
+/// The files below `root` that should be indexed, as uris. Hidden files and
+/// folders, files ignored by `.gitignore`, files excluded by `settings`,
+/// files over the size limit and binary files are left out.
+pub fn get_workspace_files(root: &Path, settings: &FileSettings) -> Vec<String> {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(true)
+        .git_ignore(true)
+        .require_git(false)
+        .max_filesize(Some(settings.max_file_size));
+    if let Some(overrides) = build_overrides(root, settings) {
+        builder.overrides(overrides);
+    }
+
+    let mut files: Vec<String> = Vec::new();
+    for entry in builder.build() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                error!("Error walking {}: {}", root.display(), e);
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) || is_binary_file(entry.path()) {
+            continue;
+        }
+        if let Ok(url) = Url::from_file_path(entry.path()) {
+            files.push(url.to_string());
+        }
+    }
+
+    info!("Found {} files in {}", files.len(), root.display());
+    files
+}
+
+/// Whether a single file below `root` would be indexed by
+/// `get_workspace_files`. Only the `.gitignore` of `root` itself is honored.
+pub fn is_workspace_file(root: &Path, path: &Path, settings: &FileSettings) -> bool {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let is_hidden = relative.components().any(|component| match component {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    });
+    if is_hidden {
+        return false;
+    }
+
+    if let Some(overrides) = build_overrides(root, settings) {
+        if overrides.matched(path, false).is_ignore() {
+            return false;
+        }
+    }
+    let (gitignore, _) = ignore::gitignore::Gitignore::new(root.join(".gitignore"));
+    if gitignore
+        .matched_path_or_any_parents(path, false)
+        .is_ignore()
+    {
+        return false;
+    }
+
+    match path.metadata() {
+        Ok(metadata) => {
+            metadata.is_file() && metadata.len() <= settings.max_file_size && !is_binary_file(path)
+        }
+        Err(_) => false,
+    }
+}
+
+/// Include globs become the override whitelist and exclude globs are
+/// negated, which is how `ignore` spells an exclusion.
+fn build_overrides(root: &Path, settings: &FileSettings) -> Option<Override> {
+    if settings.include.is_empty() && settings.exclude.is_empty() {
+        return None;
+    }
+    let mut builder = OverrideBuilder::new(root);
+    let globs = settings
+        .include
+        .iter()
+        .cloned()
+        .chain(settings.exclude.iter().map(|glob| format!("!{}", glob)));
+    for glob in globs {
+        if let Err(e) = builder.add(&glob) {
+            error!("Invalid file glob {}: {}", glob, e);
+        }
+    }
+    match builder.build() {
+        Ok(overrides) => Some(overrides),
+        Err(e) => {
+            error!("Error building file globs: {}", e);
+            None
+        }
+    }
+}
+
+/// A file is treated as binary when its start contains a NUL byte, the same
+/// heuristic git uses.
+fn is_binary_file(path: &Path) -> bool {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+    let mut buffer = [0u8; BINARY_CHECK_LENGTH];
+    let mut length = 0;
+    while length < buffer.len() {
+        match file.read(&mut buffer[length..]) {
+            Ok(0) => break,
+            Ok(read) => length += read,
+            Err(_) => return false,
+        }
+    }
+    buffer[..length].contains(&0)
+}
+
+#[test]
+fn test_get_workspace_files() {
+    use std::fs;
+
+    let root = std::env::temp_dir().join(format!("pols-files-test-{}", std::process::id()));
+    fs::create_dir_all(root.join(".git/objects")).unwrap();
+    fs::create_dir_all(root.join("build")).unwrap();
+    fs::write(root.join(".gitignore"), "build/\n*.log\n").unwrap();
+    fs::write(root.join(".git/objects/ab"), "TRUE=1").unwrap();
+    fs::write(root.join("build/OUT"), "TRUE=1").unwrap();
+    fs::write(root.join("run.log"), "TRUE=1").unwrap();
+    fs::write(root.join("IMAGE"), [0x89, 0x50, 0x00, 0x47]).unwrap();
+    fs::write(root.join("BIG.DEF"), "X".repeat(64)).unwrap();
+    fs::write(root.join("SKIP.PRO"), "PROCEDURE A\nEND\n").unwrap();
+    fs::write(root.join("COMMON.DEF"), "TRUE=1").unwrap();
+    fs::write(root.join("REPORT"), "TARGET=ACCOUNT").unwrap();
+
+    let settings = FileSettings {
+        include: Vec::new(),
+        exclude: vec!["SKIP.*".to_string()],
+        max_file_size: 32,
+        ..FileSettings::default()
+    };
+    let mut files: Vec<String> = get_workspace_files(&root, &settings)
+        .iter()
+        .map(|file| file.rsplit('/').next().unwrap().to_string())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["COMMON.DEF", "REPORT"]);
+
+    assert!(is_workspace_file(&root, &root.join("REPORT"), &settings));
+    assert!(!is_workspace_file(&root, &root.join("run.log"), &settings));
+    assert!(!is_workspace_file(&root, &root.join("SKIP.PRO"), &settings));
+    assert!(!is_workspace_file(&root, &root.join("IMAGE"), &settings));
+
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,351 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use log::error;
+use serde::{Deserialize, Serialize};
+use tower_lsp::lsp_types::{Range, TextDocumentItem};
+use tree_sitter::{QueryCursor, Tree};
+
+use crate::utils::{get_basename_from_uri, node_range};
+
+use super::queries::INCLUDE_QUERY;
+
+/// A single `#INCLUDE "FILE"` statement as written in a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncludeStatement {
+    pub name: String,
+    pub range: Range,
+}
+
+/// Directed graph of `#INCLUDE` relationships between workspace files.
+///
+/// Include statements are stored per file exactly as written, and are
+/// resolved against the known files by basename when the graph is queried.
+/// This keeps the graph correct when an included file is created or deleted
+/// after the files that include it have been indexed.
+#[derive(Debug, Default, Clone)]
+pub struct IncludeGraph {
+    includes: HashMap<String, Vec<IncludeStatement>>,
+    files_by_name: HashMap<String, Vec<String>>,
+    /// The files whose include statements name each normalized include
+    /// name, so the files including a file are found without a scan.
+    included_by: HashMap<String, HashSet<String>>,
+}
+
+impl IncludeGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the include statements of a file.
+    pub fn update_file(&mut self, uri: &str, includes: Vec<IncludeStatement>) {
+        let name = normalize_include_name(&get_basename_from_uri(uri));
+        let files = self.files_by_name.entry(name).or_default();
+        if !files.iter().any(|f| f == uri) {
+            files.push(uri.to_string());
+            files.sort();
+        }
+        self.unlink_includes(uri);
+        for include in &includes {
+            self.included_by
+                .entry(normalize_include_name(&include.name))
+                .or_default()
+                .insert(uri.to_string());
+        }
+        self.includes.insert(uri.to_string(), includes);
+    }
+
+    pub fn remove_file(&mut self, uri: &str) {
+        let name = normalize_include_name(&get_basename_from_uri(uri));
+        if let Some(files) = self.files_by_name.get_mut(&name) {
+            files.retain(|f| f != uri);
+            if files.is_empty() {
+                self.files_by_name.remove(&name);
+            }
+        }
+        self.unlink_includes(uri);
+        self.includes.remove(uri);
+    }
+
+    /// Removes the reverse edges of the include statements `uri` has now.
+    fn unlink_includes(&mut self, uri: &str) {
+        for include in self.includes.get(uri).into_iter().flatten() {
+            let name = normalize_include_name(&include.name);
+            if let Some(files) = self.included_by.get_mut(&name) {
+                files.remove(uri);
+                if files.is_empty() {
+                    self.included_by.remove(&name);
+                }
+            }
+        }
+    }
+
+    pub fn contains(&self, uri: &str) -> bool {
+        self.includes.contains_key(uri)
+    }
+
+    pub fn files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self.includes.keys().cloned().collect();
+        files.sort();
+        files
+    }
+
+    /// Resolves an include name to a workspace file. When several files share
+    /// the same basename the first one in uri order wins, so the result is
+    /// stable between runs.
+    pub fn resolve(&self, name: &str) -> Option<&String> {
+        self.files_by_name
+            .get(&normalize_include_name(name))
+            .and_then(|files| files.first())
+    }
+
+    pub fn include_statements(&self, uri: &str) -> &[IncludeStatement] {
+        match self.includes.get(uri) {
+            Some(includes) => includes.as_slice(),
+            None => &[],
+        }
+    }
+
+    /// The files directly included by `uri`, in the order they are included.
+    pub fn direct_includes(&self, uri: &str) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        for include in self.include_statements(uri) {
+            if let Some(target) = self.resolve(&include.name) {
+                if !result.contains(target) {
+                    result.push(target.to_string());
+                }
+            }
+        }
+        result
+    }
+
+    /// The files that include `uri` directly. A file that shares its name
+    /// with an earlier file is never the one an include resolves to.
+    pub fn direct_includers(&self, uri: &str) -> Vec<String> {
+        let name = normalize_include_name(&get_basename_from_uri(uri));
+        if self.resolve(&name).map(String::as_str) != Some(uri) {
+            return Vec::new();
+        }
+        let mut result: Vec<String> = match self.included_by.get(&name) {
+            Some(files) => files.iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        result.sort();
+        result
+    }
+
+    /// Every file reachable from `uri` through includes, at any depth, in the
+    /// order the compiler would encounter them. `uri` itself is not part of
+    /// the result unless it includes itself through a cycle.
+    pub fn include_closure(&self, uri: &str) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        let mut visited: HashSet<String> = HashSet::new();
+        visited.insert(uri.to_string());
+        self.visit_includes(uri, &mut visited, &mut result);
+        result
+    }
+
+    fn visit_includes(&self, uri: &str, visited: &mut HashSet<String>, result: &mut Vec<String>) {
+        for include in self.direct_includes(uri) {
+            if visited.insert(include.clone()) {
+                result.push(include.clone());
+                self.visit_includes(&include, visited, result);
+            }
+        }
+    }
+
+    /// Every file that includes `uri`, directly or transitively.
+    pub fn reverse_closure(&self, uri: &str) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        let mut visited: HashSet<String> = HashSet::new();
+        let mut queue: VecDeque<String> = VecDeque::from([uri.to_string()]);
+        visited.insert(uri.to_string());
+
+        while let Some(current) = queue.pop_front() {
+            for file in self.direct_includers(&current) {
+                if visited.insert(file.clone()) {
+                    result.push(file.clone());
+                    queue.push_back(file);
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    /// Whether any file includes `uri`.
+    pub fn is_included(&self, uri: &str) -> bool {
+        !self.direct_includers(uri).is_empty()
+    }
+
+    /// The top-level files that include `uri`, directly or transitively.
+    /// These are the drivers whose compilation pulls `uri` in.
+    pub fn drivers_of(&self, uri: &str) -> Vec<String> {
+        self.reverse_closure(uri)
+            .into_iter()
+            .filter(|file| !self.is_included(file))
+            .collect()
+    }
+
+    /// Shortest include path from `from` to `to`, both ends included.
+    pub fn include_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut queue: VecDeque<String> = VecDeque::from([from.to_string()]);
+        let mut visited: HashSet<String> = HashSet::from([from.to_string()]);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![current.clone()];
+                let mut node = current;
+                while let Some(parent) = parents.get(&node) {
+                    path.push(parent.clone());
+                    node = parent.clone();
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for include in self.direct_includes(&current) {
+                if visited.insert(include.clone()) {
+                    parents.insert(include.clone(), current.clone());
+                    queue.push_back(include);
+                }
+            }
+        }
+        None
+    }
+
+    /// Include statements in `uri` that lead back to `uri`, with the cycle
+    /// each one closes. The cycle starts and ends with `uri`.
+    pub fn cycles_from(&self, uri: &str) -> Vec<(IncludeStatement, Vec<String>)> {
+        let mut cycles = Vec::new();
+        for include in self.include_statements(uri) {
+            let target = match self.resolve(&include.name) {
+                Some(target) => target,
+                None => continue,
+            };
+            if let Some(path) = self.include_path(target, uri) {
+                let mut cycle = vec![uri.to_string()];
+                cycle.extend(path);
+                cycles.push((include.clone(), cycle));
+            }
+        }
+        cycles
+    }
+}
+
+/// Include names are matched case-insensitively on their basename, the way
+/// the host resolves them.
+pub fn normalize_include_name(name: &str) -> String {
+    let name = name.trim().trim_matches('"');
+    let name = match name.rsplit(['/', '\\']).next() {
+        Some(name) => name,
+        None => name,
+    };
+    name.to_uppercase()
+}
+
+pub fn get_include_statements(document: &TextDocumentItem, tree: &Tree) -> Vec<IncludeStatement> {
+    let source = document.text.as_str();
+    let mut cursor = QueryCursor::new();
+    let matches = cursor.matches(&INCLUDE_QUERY, tree.root_node(), source.as_bytes());
+    let mut includes: Vec<IncludeStatement> = Vec::new();
+    for m in matches {
+        let node = m.captures[0].node;
+        let name = match node.utf8_text(source.as_bytes()) {
+            Ok(name) => name.replace('"', ""),
+            Err(e) => {
+                error!("error getting utf8 text: {}", e);
+                continue;
+            }
+        };
+        let range = match node.parent() {
+            Some(parent) => node_range(&parent, source),
+            None => node_range(&node, source),
+        };
+        includes.push(IncludeStatement { name, range });
+    }
+    includes
+}
+
+#[cfg(test)]
+fn include(name: &str) -> IncludeStatement {
+    IncludeStatement {
+        name: name.to_string(),
+        range: Range::default(),
+    }
+}
+
+#[test]
+fn test_include_closure_is_transitive() {
+    let mut graph = IncludeGraph::new();
+    graph.update_file("file:///specs/DRIVER", vec![include("A.DEF")]);
+    graph.update_file("file:///specs/A.DEF", vec![include("b.def")]);
+    graph.update_file("file:///specs/B.DEF", vec![include("C.DEF")]);
+    graph.update_file("file:///specs/C.DEF", vec![include("D.DEF")]);
+    graph.update_file("file:///specs/D.DEF", vec![]);
+
+    assert_eq!(
+        graph.include_closure("file:///specs/DRIVER"),
+        vec![
+            "file:///specs/A.DEF",
+            "file:///specs/B.DEF",
+            "file:///specs/C.DEF",
+            "file:///specs/D.DEF",
+        ]
+    );
+    assert_eq!(
+        graph.reverse_closure("file:///specs/C.DEF"),
+        vec![
+            "file:///specs/A.DEF",
+            "file:///specs/B.DEF",
+            "file:///specs/DRIVER",
+        ]
+    );
+    assert_eq!(
+        graph.drivers_of("file:///specs/C.DEF"),
+        vec!["file:///specs/DRIVER"]
+    );
+}
+
+#[test]
+fn test_include_cycles_are_reported() {
+    let mut graph = IncludeGraph::new();
+    graph.update_file("file:///specs/A.DEF", vec![include("B.DEF")]);
+    graph.update_file("file:///specs/B.DEF", vec![include("C.DEF")]);
+    graph.update_file("file:///specs/C.DEF", vec![include("A.DEF")]);
+
+    let cycles = graph.cycles_from("file:///specs/B.DEF");
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(
+        cycles[0].1,
+        vec![
+            "file:///specs/B.DEF",
+            "file:///specs/C.DEF",
+            "file:///specs/A.DEF",
+            "file:///specs/B.DEF",
+        ]
+    );
+
+    graph.update_file("file:///specs/C.DEF", vec![]);
+    assert!(graph.cycles_from("file:///specs/B.DEF").is_empty());
+}
+
+#[test]
+fn test_includers_follow_updates() {
+    let mut graph = IncludeGraph::new();
+    graph.update_file("file:///specs/DRIVER", vec![include("A.DEF")]);
+    assert!(graph.reverse_closure("file:///specs/A.DEF").is_empty());
+
+    // The included file may be indexed after the files that include it.
+    graph.update_file("file:///specs/A.DEF", vec![]);
+    assert!(graph.is_included("file:///specs/A.DEF"));
+    assert_eq!(
+        graph.direct_includers("file:///specs/A.DEF"),
+        vec!["file:///specs/DRIVER"]
+    );
+
+    graph.update_file("file:///specs/DRIVER", vec![include("B.DEF")]);
+    assert!(!graph.is_included("file:///specs/A.DEF"));
+    graph.update_file("file:///specs/DRIVER", vec![include("A.DEF")]);
+    graph.remove_file("file:///specs/DRIVER");
+    assert!(!graph.is_included("file:///specs/A.DEF"));
+}
@@ -0,0 +1,221 @@
+pub mod analysis;
+pub mod cache;
+pub mod file_kind;
+pub mod files;
+pub mod include_graph;
+pub mod queries;
+pub mod record_scope;
+pub mod symbols;
+
+use std::sync::{Arc, MutexGuard};
+
+use crate::{
+    state::{Document, ServerState, Snapshot},
+    sync::lock_or_reset,
+};
+
+use self::{
+    cache::{CachedFile, FileState, IndexCache},
+    file_kind::{classify_file, FileKind},
+    include_graph::{get_include_statements, IncludeStatement},
+    symbols::{get_file_symbols, FileSymbols},
+};
+
+/// Adds a document to `snapshot` along with everything the workspace index
+/// knows about it. Parse the document before editing the server state.
+pub fn index_document(snapshot: &mut Snapshot, document: Document) {
+    let uri = document.item.uri.to_string();
+    let path = document.item.uri.to_file_path().ok();
+    let kind = classify_file(path.as_deref(), document.tree());
+    let (includes, file_symbols) = match document.tree() {
+        Some(tree) => (
+            get_include_statements(&document.item, tree),
+            get_file_symbols(&document.item, tree),
+        ),
+        None => (Vec::new(), FileSymbols::default()),
+    };
+    Arc::make_mut(&mut snapshot.documents).insert(uri.clone(), Arc::new(document));
+    store_document(snapshot, &uri, kind, includes, file_symbols);
+}
+
+/// Adds a document to `snapshot` with the analysis saved in the index
+/// cache, without parsing it.
+pub fn restore_document(snapshot: &mut Snapshot, document: Document, cached: &CachedFile) {
+    let uri = document.item.uri.to_string();
+    Arc::make_mut(&mut snapshot.documents).insert(uri.clone(), Arc::new(document));
+    store_document(
+        snapshot,
+        &uri,
+        Some(cached.kind),
+        cached.includes.clone(),
+        cached.symbols.clone(),
+    );
+}
+
+fn store_document(
+    snapshot: &mut Snapshot,
+    uri: &str,
+    kind: Option<FileKind>,
+    includes: Vec<IncludeStatement>,
+    file_symbols: FileSymbols,
+) {
+    // A file with no kind is not cached, so it is classified again once it
+    // parses.
+    match kind {
+        Some(kind) => set_file_kind(snapshot, uri, kind),
+        None => {
+            Arc::make_mut(&mut snapshot.file_kinds).remove(uri);
+        }
+    }
+    let graph_changed = !snapshot.include_graph.contains(uri)
+        || snapshot.include_graph.include_statements(uri) != includes.as_slice();
+    if graph_changed {
+        Arc::make_mut(&mut snapshot.include_graph).update_file(uri, includes);
+        snapshot.graph_revision = snapshot.revision;
+    }
+    Arc::make_mut(&mut snapshot.symbols).insert(uri.to_string(), Arc::new(file_symbols));
+    Arc::make_mut(&mut snapshot.file_revisions).insert(uri.to_string(), snapshot.revision);
+}
+
+pub fn remove_document(snapshot: &mut Snapshot, uri: &str) {
+    snapshot.graph_revision = snapshot.revision;
+    Arc::make_mut(&mut snapshot.file_revisions).remove(uri);
+    snapshot.analysis.forget(uri);
+    Arc::make_mut(&mut snapshot.documents).remove(uri);
+    Arc::make_mut(&mut snapshot.include_graph).remove_file(uri);
+    Arc::make_mut(&mut snapshot.symbols).remove(uri);
+    Arc::make_mut(&mut snapshot.file_kinds).remove(uri);
+}
+
+/// Records the kind of `uri`. Files that are not indexed, such as letter
+/// files, only have a kind.
+pub fn set_file_kind(snapshot: &mut Snapshot, uri: &str, kind: FileKind) {
+    Arc::make_mut(&mut snapshot.file_kinds).insert(uri.to_string(), kind);
+}
+
+pub fn get_file_kind(snapshot: &Snapshot, uri: &str) -> Option<FileKind> {
+    snapshot.file_kinds.get(uri).copied()
+}
+
+/// Saves the analysis of `uri` in `snapshot` to the index cache. Only call
+/// this while the indexed document matches the file on disk described by
+/// `state`.
+pub fn cache_document(server: &ServerState, snapshot: &Snapshot, uri: &str, state: FileState) {
+    let kind = match get_file_kind(snapshot, uri) {
+        Some(kind) => kind,
+        None => return,
+    };
+    let cached = CachedFile {
+        state,
+        kind,
+        includes: snapshot.include_graph.include_statements(uri).to_vec(),
+        symbols: snapshot
+            .symbols
+            .get(uri)
+            .map(|symbols| symbols.as_ref().clone())
+            .unwrap_or_default(),
+    };
+    lock_index_cache(server).insert(uri, cached);
+}
+
+/// Drops `uri` from the index cache, for instance once it has been edited
+/// and no longer matches the file on disk.
+pub fn uncache_document(server: &ServerState, uri: &str) {
+    lock_index_cache(server).remove(uri);
+}
+
+pub fn save_index_cache(server: &ServerState) {
+    lock_index_cache(server).save();
+}
+
+/// Locks the index cache. A panic while it was locked may have left entries
+/// half-written, so the cache is then emptied and refills as files are
+/// indexed again.
+pub fn lock_index_cache(server: &ServerState) -> MutexGuard<'_, IndexCache> {
+    lock_or_reset(&server.index_cache, |cache| {
+        *cache = IndexCache::new(cache.path().cloned())
+    })
+}
+
+/// The files in the include closure of `uri`, not counting `uri` itself.
+pub fn get_include_closure(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    snapshot.include_graph.include_closure(uri)
+}
+
+/// The drivers that include `uri`, directly or transitively.
+pub fn get_drivers(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    snapshot.include_graph.drivers_of(uri)
+}
+
+/// The drivers used to analyze an include file: the active driver when one
+/// has been chosen for it, otherwise every driver that includes it.
+pub fn get_context_drivers(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    let drivers = get_drivers(snapshot, uri);
+    match snapshot.active_drivers.get(uri) {
+        Some(active_driver) if drivers.contains(active_driver) => vec![active_driver.clone()],
+        _ => drivers,
+    }
+}
+
+pub fn set_active_driver(snapshot: &mut Snapshot, uri: &str, driver: Option<String>) {
+    snapshot.graph_revision = snapshot.revision;
+    let active_drivers = Arc::make_mut(&mut snapshot.active_drivers);
+    match driver {
+        Some(driver) => {
+            active_drivers.insert(uri.to_string(), driver);
+        }
+        None => {
+            active_drivers.remove(uri);
+        }
+    }
+}
+
+/// Every file visible when analyzing `uri`. For a file that is included by
+/// drivers this is the union of the context drivers and their include
+/// closures, so a PRO file can see the DEF files its drivers include. For
+/// anything else it is the file and its own include closure.
+pub fn get_analysis_files(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    let drivers = get_context_drivers(snapshot, uri);
+    let graph = &snapshot.include_graph;
+    let roots = match drivers.is_empty() {
+        true => vec![uri.to_string()],
+        false => drivers,
+    };
+
+    let mut files: Vec<String> = Vec::new();
+    for root in roots {
+        let closure = graph.include_closure(&root);
+        for file in std::iter::once(root).chain(closure) {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    if !files.iter().any(|file| file == uri) {
+        files.insert(0, uri.to_string());
+    }
+    files
+}
+
+/// Files whose analysis can change when `uri` changes: the file itself, the
+/// files it includes, the files that include it and everything else pulled
+/// in by the same drivers.
+pub fn get_affected_files(snapshot: &Snapshot, uri: &str) -> Vec<String> {
+    let graph = &snapshot.include_graph;
+    let mut files = vec![uri.to_string()];
+    let drivers = graph.drivers_of(uri);
+    let driver_files = drivers
+        .iter()
+        .flat_map(|driver| graph.include_closure(driver));
+    for file in graph
+        .include_closure(uri)
+        .into_iter()
+        .chain(graph.reverse_closure(uri))
+        .chain(driver_files)
+    {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    files
+}
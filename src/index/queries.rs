@@ -0,0 +1,20 @@
+use lazy_static::lazy_static;
+use tree_sitter::Query;
+
+// Queries are compiled once and shared, since compiling one costs far more
+// than running it over a file.
+lazy_static! {
+    pub static ref SYMBOL_QUERY: Query =
+        compile("(variable_declaration) @var (procedure_definition) @proc (database_field) @field");
+    pub static ref INCLUDE_QUERY: Query = compile("(include_statement (string_literal) @inc)");
+}
+
+fn compile(source: &str) -> Query {
+    Query::new(tree_sitter_poweron::language(), source).unwrap()
+}
+
+#[test]
+fn test_queries_compile() {
+    lazy_static::initialize(&SYMBOL_QUERY);
+    lazy_static::initialize(&INCLUDE_QUERY);
+}
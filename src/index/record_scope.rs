@@ -0,0 +1,296 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tree_sitter::{Node, Tree};
+
+use crate::{
+    cancellation::{Cancellable, CancellationToken},
+    database::record_types::{is_record_type, normalize_record_type, record_ancestry},
+    state::{Document, Snapshot},
+};
+
+/// Nodes whose records are current inside them: loops over records and the
+/// expressions that test a record `WITH` a condition.
+const SCOPE_KINDS: &[&str] = &[
+    "foreachdoend",
+    "forrecord",
+    "forrecordwith",
+    "any_with_expression",
+    "not_with_expression",
+    "not_any_with_expression",
+];
+
+/// The records current in each procedure, keyed by upper-case name.
+pub type ProcedureRecords = HashMap<String, Vec<String>>;
+
+/// The records the TARGET division makes current everywhere, with the
+/// records they belong to. Empty when the file has no TARGET.
+pub fn target_records(root: Node, text: &str) -> Vec<String> {
+    let mut cursor = root.walk();
+    let target = root
+        .children(&mut cursor)
+        .find(|child| child.kind() == "target_division");
+    match target {
+        Some(target) => with_ancestry(record_types(target, text)),
+        None => Vec::new(),
+    }
+}
+
+/// The records `node` makes current inside it, or nothing when it is not a
+/// loop or a `WITH` expression.
+pub fn scope_records(node: Node, text: &str) -> Vec<String> {
+    if SCOPE_KINDS.contains(&node.kind()) {
+        record_types(node, text)
+    } else {
+        Vec::new()
+    }
+}
+
+/// The record a `database_field` node reads.
+pub fn field_record(node: Node, text: &str) -> Option<String> {
+    let records = record_types(node, text);
+    let record = normalize_record_type(&records.join(" "));
+    is_record_type(&record).then_some(record)
+}
+
+/// The records current at `byte`. In a procedure they are those in
+/// `procedures`, as worked out by `get_procedure_records`. None where they
+/// are not known: in a procedure missing from `procedures`, and outside
+/// procedures in a file without TARGET.
+pub fn records_at(
+    tree: &Tree,
+    text: &str,
+    byte: usize,
+    procedures: &HashMap<String, Vec<String>>,
+) -> Option<Vec<String>> {
+    let root = tree.root_node();
+    let mut records = Vec::new();
+    let mut node = root.descendant_for_byte_range(byte, byte);
+    while let Some(current) = node {
+        if current.kind() == "procedure_definition" {
+            let name = procedure_name(current, text)?;
+            records.extend(procedures.get(&name)?.iter().cloned());
+            return Some(with_ancestry(records));
+        }
+        // A loop's own record is not current in its header.
+        let header_end = last_record_type(current).map_or(0, |record| record.end_byte());
+        if header_end < byte {
+            records.extend(scope_records(current, text));
+        }
+        node = current.parent();
+    }
+    let target = target_records(root, text);
+    if target.is_empty() {
+        return None;
+    }
+    Some(with_ancestry([target, records].concat()))
+}
+
+/// The records current in each procedure `driver` calls, keyed by upper-case
+/// name. Those are the records current at every call to it, from the main
+/// body or from other procedures, in `driver` and the files it includes.
+/// Procedures that are never called are left out.
+pub fn get_procedure_records(
+    snapshot: &Snapshot,
+    driver: &str,
+    token: &CancellationToken,
+) -> Cancellable<ProcedureRecords> {
+    let mut procedures = ProcedureRecords::new();
+    let documents: Vec<Arc<Document>> = std::iter::once(driver.to_string())
+        .chain(snapshot.include_graph.include_closure(driver))
+        .filter_map(|uri| snapshot.document(&uri))
+        .collect();
+    let (root, text) = match documents.first().and_then(|document| document.tree()) {
+        Some(tree) => (tree.root_node(), documents[0].item.text.as_str()),
+        None => return Ok(procedures),
+    };
+    let target = target_records(root, text);
+    if target.is_empty() {
+        return Ok(procedures);
+    }
+
+    let mut definitions: HashMap<String, (Node, &str)> = HashMap::new();
+    for document in &documents {
+        token.check()?;
+        if let Some(tree) = document.tree() {
+            let text = document.item.text.as_str();
+            for definition in procedure_definitions(tree.root_node()) {
+                if let Some(name) = procedure_name(definition, text) {
+                    definitions.entry(name).or_insert((definition, text));
+                }
+            }
+        }
+    }
+
+    let mut calls: Vec<(String, Vec<String>)> = Vec::new();
+    collect_calls(root, text, &target, &mut calls);
+    while let Some((name, records)) = calls.pop() {
+        token.check()?;
+        // A record is only current in a procedure if it is current at every
+        // call, so each call can only narrow what is known.
+        let changed = match procedures.get_mut(&name) {
+            Some(current) => {
+                let count = current.len();
+                current.retain(|record| records.contains(record));
+                current.len() < count
+            }
+            None => {
+                procedures.insert(name.clone(), records);
+                true
+            }
+        };
+        if let Some((definition, text)) = definitions.get(&name).filter(|_| changed) {
+            let mut cursor = definition.walk();
+            for child in definition.children(&mut cursor) {
+                collect_calls(child, text, &procedures[&name], &mut calls);
+            }
+        }
+    }
+    Ok(procedures)
+}
+
+/// The procedure a `procedure_definition` or `procedure_call` node names.
+pub fn procedure_name(node: Node, text: &str) -> Option<String> {
+    let name = node.named_child(0)?.utf8_text(text.as_bytes()).ok()?;
+    Some(name.to_uppercase())
+}
+
+/// The calls under `node` outside procedure definitions, with the records
+/// current at each.
+fn collect_calls(
+    node: Node,
+    text: &str,
+    records: &[String],
+    calls: &mut Vec<(String, Vec<String>)>,
+) {
+    match node.kind() {
+        "procedure_definition" => return,
+        "procedure_call" => {
+            if let Some(name) = procedure_name(node, text) {
+                calls.push((name, records.to_vec()));
+            }
+        }
+        _ => {}
+    }
+    let records = with_ancestry([records.to_vec(), scope_records(node, text)].concat());
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_calls(child, text, &records, calls);
+    }
+}
+
+fn procedure_definitions(node: Node) -> Vec<Node> {
+    if node.kind() == "procedure_definition" {
+        return vec![node];
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children
+        .into_iter()
+        .flat_map(procedure_definitions)
+        .collect()
+}
+
+/// Adds the records each of `records` belongs to, once each.
+pub fn with_ancestry(records: Vec<String>) -> Vec<String> {
+    let mut all: Vec<String> = Vec::new();
+    for record in records {
+        for record in record_ancestry(&record) {
+            if !all.contains(&record) {
+                all.push(record);
+            }
+        }
+    }
+    all
+}
+
+fn record_types(node: Node, text: &str) -> Vec<String> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| child.kind() == "record_type")
+        .filter_map(|child| child.utf8_text(text.as_bytes()).ok())
+        .map(normalize_record_type)
+        .collect()
+}
+
+fn last_record_type(node: Node) -> Option<Node> {
+    let mut cursor = node.walk();
+    let last = node
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "record_type")
+        .last();
+    last
+}
+
+#[test]
+fn test_records_at() {
+    let text = "TARGET=SHARE\nPRINT TITLE=\"X\"\n FOR EACH SHARE TRANSFER\n  DO\n   X=1\n  END\n \
+                Y=2\nEND\nPROCEDURE P\n Z=3\nEND\n";
+    let tree = crate::parser::parse(text).unwrap();
+    let procedures = HashMap::from([("P".to_string(), vec!["LOAN".to_string()])]);
+    let at = |needle: &str| records_at(&tree, text, text.find(needle).unwrap(), &procedures);
+
+    assert_eq!(
+        at("X=1"),
+        Some(vec![
+            "SHARE".to_string(),
+            "ACCOUNT".to_string(),
+            "SHARE TRANSFER".to_string()
+        ])
+    );
+    assert_eq!(
+        at("Y=2"),
+        Some(vec!["SHARE".to_string(), "ACCOUNT".to_string()])
+    );
+    assert_eq!(
+        at("Z=3"),
+        Some(vec!["LOAN".to_string(), "ACCOUNT".to_string()])
+    );
+    assert_eq!(
+        records_at(&tree, text, text.find("Z=3").unwrap(), &HashMap::new()),
+        None
+    );
+}
+
+#[test]
+fn test_procedure_records() {
+    use crate::test_utils::snapshot_with;
+
+    let driver = "file:///specs/DRIVER";
+    let snapshot = snapshot_with(&[
+        (
+            driver,
+            "TARGET=ACCOUNT\nPRINT TITLE=\"X\"\n FOR EACH LOAN\n  DO\n   CALL BOTH\n   CALL LOANONLY\n  \
+             END\n FOR EACH SHARE\n  DO\n   CALL BOTH\n  END\nEND\n#INCLUDE \"PROCS.PRO\"\n",
+        ),
+        (
+            "file:///specs/PROCS.PRO",
+            "PROCEDURE BOTH\nEND\nPROCEDURE LOANONLY\n CALL NESTED\nEND\nPROCEDURE NESTED\nEND\n\
+             PROCEDURE UNUSED\nEND\n",
+        ),
+    ]);
+
+    let token = CancellationToken::new();
+    let procedures = get_procedure_records(&snapshot, driver, &token).unwrap();
+    let records = |name: &str| procedures.get(name).cloned();
+    let strings = |records: &[&str]| records.iter().map(|record| record.to_string()).collect();
+    assert_eq!(records("BOTH"), Some(strings(&["ACCOUNT"])));
+    assert_eq!(records("LOANONLY"), Some(strings(&["ACCOUNT", "LOAN"])));
+    assert_eq!(records("NESTED"), Some(strings(&["ACCOUNT", "LOAN"])));
+    assert_eq!(records("UNUSED"), None);
+
+    let memoized = snapshot
+        .analysis
+        .procedure_records(&snapshot, driver, &token)
+        .unwrap();
+    assert_eq!(*memoized, procedures);
+    assert!(Arc::ptr_eq(
+        &memoized,
+        &snapshot
+            .analysis
+            .procedure_records(&snapshot, driver, &token)
+            .unwrap()
+    ));
+
+    token.cancel();
+    assert!(get_procedure_records(&snapshot, driver, &token).is_err());
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use log::error;
+use serde::{Deserialize, Serialize};
+use tower_lsp::lsp_types::{Range, TextDocumentItem};
+use tree_sitter::{Node, QueryCursor, Tree};
+
+use crate::{
+    database::{record_types::normalize_record_type, types::DataType},
+    utils::node_range,
+};
+
+use super::queries::SYMBOL_QUERY;
+
+/// A variable declared in a DEFINE division or a DEF file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariableSymbol {
+    pub name: String,
+    pub data_type: Option<DataType>,
+    pub size: Option<u32>,
+    pub is_array: bool,
+    /// Range of the identifier being declared.
+    pub range: Range,
+    /// Range of the whole declaration.
+    pub declaration_range: Range,
+}
+
+impl VariableSymbol {
+    /// The declared type as it would be written in a DEFINE division.
+    pub fn type_description(&self) -> String {
+        let mut description = match self.data_type {
+            Some(data_type) => data_type.as_str().to_string(),
+            None => "UNKNOWN".to_string(),
+        };
+        if let Some(size) = self.size {
+            description.push_str(&format!("({})", size));
+        }
+        if self.is_array {
+            description.push_str(" ARRAY");
+        }
+        description
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcedureSymbol {
+    pub name: String,
+    pub range: Range,
+    pub definition_range: Range,
+}
+
+/// The declarations made by a single file, not counting its includes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileSymbols {
+    pub variables: Vec<VariableSymbol>,
+    pub procedures: Vec<ProcedureSymbol>,
+    /// How often the file reads or writes each field, keyed by
+    /// `field_reference_key`.
+    pub field_references: HashMap<String, u32>,
+}
+
+/// Names a field of a record, such as `ACCOUNT:OPENDATE`.
+pub fn field_reference_key(record: &str, field: &str) -> String {
+    format!(
+        "{}:{}",
+        normalize_record_type(record),
+        field.trim().to_uppercase()
+    )
+}
+
+pub fn get_file_symbols(document: &TextDocumentItem, tree: &Tree) -> FileSymbols {
+    let source = document.text.as_str();
+    let mut symbols = FileSymbols::default();
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&SYMBOL_QUERY, tree.root_node(), source.as_bytes()) {
+        let node = m.captures[0].node;
+        match node.kind() {
+            "variable_declaration" => {
+                if let Some(variable) = get_variable_symbol(&node, source) {
+                    symbols.variables.push(variable);
+                }
+            }
+            "procedure_definition" => {
+                if let Some(procedure) = get_procedure_symbol(&node, source) {
+                    symbols.procedures.push(procedure);
+                }
+            }
+            "database_field" => {
+                if let Some(field) = get_field_reference(&node, source) {
+                    *symbols.field_references.entry(field).or_default() += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    symbols
+}
+
+fn get_variable_symbol(node: &Node, text: &str) -> Option<VariableSymbol> {
+    let source = text.as_bytes();
+    let identifier = node.named_child(0)?;
+    let name = match identifier.utf8_text(source) {
+        Ok(name) => name.trim().to_string(),
+        Err(e) => {
+            error!("Error getting utf8 text: {}", e);
+            return None;
+        }
+    };
+
+    let mut data_type: Option<DataType> = None;
+    let mut size: Option<u32> = None;
+    let mut is_array = false;
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor).skip(1) {
+        match child.kind() {
+            "data_type" => {
+                let text = child.utf8_text(source).unwrap_or_default();
+                let type_name = text.split('(').next().unwrap_or_default();
+                data_type = DataType::from_name(type_name);
+                size = child
+                    .child_by_field_name("size")
+                    .and_then(|size| size.utf8_text(source).ok())
+                    .and_then(|size| size.trim().parse().ok());
+            }
+            "array_type" => is_array = true,
+            "poweron_function" => {
+                data_type = child
+                    .named_child(0)
+                    .and_then(|function| match function.kind() {
+                        "numberfn" => Some(DataType::Number),
+                        "moneyfn" => Some(DataType::Money),
+                        "datefn" => Some(DataType::Date),
+                        "ratefn" => Some(DataType::Rate),
+                        "floatfn" => Some(DataType::Float),
+                        _ => None,
+                    });
+            }
+            "string_literal" => data_type = Some(DataType::Character),
+            "number" => data_type = Some(DataType::Number),
+            "date" => data_type = Some(DataType::Date),
+            "rate" => data_type = Some(DataType::Rate),
+            _ => {}
+        }
+    }
+
+    Some(VariableSymbol {
+        name,
+        data_type,
+        size,
+        is_array,
+        range: node_range(&identifier, text),
+        declaration_range: node_range(node, text),
+    })
+}
+
+/// The field a `database_field` node names. The field name node holds the
+/// field's own name, followed by any subscripts.
+fn get_field_reference(node: &Node, text: &str) -> Option<String> {
+    let source = text.as_bytes();
+    let mut records: Vec<&str> = Vec::new();
+    let mut field: Option<&str> = None;
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "record_type" => records.push(child.utf8_text(source).ok()?),
+            "field_name" => {
+                let name = child.named_child(0).unwrap_or(child);
+                field = name.utf8_text(source).ok()?.split(':').next();
+            }
+            _ => {}
+        }
+    }
+    if records.is_empty() {
+        return None;
+    }
+    Some(field_reference_key(&records.join(" "), field?))
+}
+
+fn get_procedure_symbol(node: &Node, text: &str) -> Option<ProcedureSymbol> {
+    let identifier = node.named_child(0)?;
+    let name = match identifier.utf8_text(text.as_bytes()) {
+        Ok(name) => name.trim().to_string(),
+        Err(e) => {
+            error!("Error getting utf8 text: {}", e);
+            return None;
+        }
+    };
+    Some(ProcedureSymbol {
+        name,
+        range: node_range(&identifier, text),
+        definition_range: node_range(node, text),
+    })
+}
+
+#[test]
+fn test_get_file_symbols() {
+    let source = "TARGET=ACCOUNT\nDEFINE\n TRUE=1\n NAME=CHARACTER(40) ARRAY(5)\n AMT=MONEY\nEND\n\
+                  PRINT TITLE=\"TEST\"\n CALL PRINTHEADER\nEND\nPROCEDURE PRINTHEADER\nEND\n";
+    let document = crate::test_utils::document_item("file:///specs/TEST", source);
+    let tree = crate::parser::get_parser().parse(source, None).unwrap();
+    let symbols = get_file_symbols(&document, &tree);
+
+    let types: Vec<(String, String)> = symbols
+        .variables
+        .iter()
+        .map(|v| (v.name.clone(), v.type_description()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("TRUE".to_string(), "NUMBER".to_string()),
+            ("NAME".to_string(), "CHARACTER(40) ARRAY".to_string()),
+            ("AMT".to_string(), "MONEY".to_string()),
+        ]
+    );
+    assert_eq!(symbols.procedures.len(), 1);
+    assert_eq!(symbols.procedures[0].name, "PRINTHEADER");
+}
+
+#[test]
+fn test_field_references() {
+    let source =
+        "TARGET=ACCOUNT\nPRINT TITLE=\"TEST\"\n IF ACCOUNT:OPENDATE=ACCOUNT:CLOSEDATE THEN\n  \
+                  PRINT ACCOUNT:opendate\nEND\n";
+    let document = crate::test_utils::document_item("file:///specs/TEST", source);
+    let tree = crate::parser::get_parser().parse(source, None).unwrap();
+    let symbols = get_file_symbols(&document, &tree);
+
+    let references: HashMap<String, u32> = [
+        ("ACCOUNT:OPENDATE".to_string(), 2),
+        ("ACCOUNT:CLOSEDATE".to_string(), 1),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(symbols.field_references, references);
+}
@@ -1,7 +1,18 @@
+pub mod cancellation;
 pub mod cli;
 pub mod completions;
 pub mod database;
+pub mod diagnostics;
 pub mod handlers;
+pub mod index;
+pub mod logging;
 pub mod lsp;
+pub mod panics;
 pub mod parser;
+pub mod settings;
+pub mod state;
+pub mod sync;
+#[cfg(test)]
+mod test_utils;
+pub mod text;
 pub mod utils;
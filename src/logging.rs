@@ -0,0 +1,245 @@
+use std::{
+    env,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex, MutexGuard, PoisonError, Weak},
+};
+
+use lazy_static::lazy_static;
+use log::{Level, LevelFilter, Record};
+use log4rs::{
+    append::{
+        console::{ConsoleAppender, Target},
+        file::FileAppender,
+        Append,
+    },
+    config::{Appender, Config, Root},
+    encode::pattern::PatternEncoder,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tower_lsp::{
+    lsp_types::{notification::LogTrace, LogTraceParams, MessageType, SetTraceParams, TraceValue},
+    Client,
+};
+
+const LOG_PATTERN: &str = "{d(%Y-%m-%d %H:%M:%S)(utc)} - {h({l})}: {m}{n}";
+
+/// Where log records go. Command line flags win over the `POLS_LOG_LEVEL`,
+/// `POLS_LOG_FILE` and `POLS_LOG_CONFIG` environment variables, which win
+/// over the defaults: info and above, written to stderr.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSettings {
+    pub level: LevelFilter,
+    /// Log to this file rather than to stderr.
+    pub file: Option<PathBuf>,
+    /// A log4rs configuration file that replaces the settings above.
+    pub config: Option<PathBuf>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            file: None,
+            config: None,
+        }
+    }
+}
+
+impl LogSettings {
+    pub fn from_env() -> Self {
+        let mut settings = Self::default();
+        if let Ok(level) = env::var("POLS_LOG_LEVEL") {
+            settings.set_level(&level);
+        }
+        if let Ok(file) = env::var("POLS_LOG_FILE") {
+            settings.file = Some(PathBuf::from(file));
+        }
+        if let Ok(config) = env::var("POLS_LOG_CONFIG") {
+            settings.config = Some(PathBuf::from(config));
+        }
+        settings
+    }
+
+    /// Sets the level from its name, such as `debug`. Unknown names keep the
+    /// current level.
+    pub fn set_level(&mut self, level: &str) {
+        match LevelFilter::from_str(level) {
+            Ok(level) => self.level = level,
+            Err(_) => eprintln!("Unknown log level {}, using {}", level, self.level),
+        }
+    }
+}
+
+/// Installs the global logger. A log configuration that cannot be read or a
+/// log file that cannot be opened falls back to stderr instead of stopping
+/// the server.
+pub fn init_logging(settings: &LogSettings) {
+    if let Some(path) = &settings.config {
+        match log4rs::init_file(path, Default::default()) {
+            Ok(()) => return,
+            Err(e) => eprintln!("Error reading log config {}: {}", path.display(), e),
+        }
+    }
+
+    let config = match build_config(settings) {
+        Some(config) => config,
+        None => return,
+    };
+    if let Err(e) = log4rs::init_config(config) {
+        eprintln!("Error initializing logging: {}", e);
+    }
+}
+
+fn build_config(settings: &LogSettings) -> Option<Config> {
+    let encoder = || Box::new(PatternEncoder::new(LOG_PATTERN));
+    let mut output: Option<Box<dyn Append>> = None;
+    if let Some(file) = &settings.file {
+        match FileAppender::builder().encoder(encoder()).build(file) {
+            Ok(appender) => output = Some(Box::new(appender)),
+            Err(e) => eprintln!("Error opening log file {}: {}", file.display(), e),
+        }
+    }
+    let output = output.unwrap_or_else(|| {
+        Box::new(
+            ConsoleAppender::builder()
+                .encoder(encoder())
+                .target(Target::Stderr)
+                .build(),
+        )
+    });
+
+    let config = Config::builder()
+        .appender(Appender::builder().build("output", output))
+        .appender(Appender::builder().build("client", Box::new(ClientAppender)))
+        .build(
+            Root::builder()
+                .appenders(["output", "client"])
+                .build(settings.level),
+        );
+    match config {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("Error building log config: {}", e);
+            None
+        }
+    }
+}
+
+lazy_static! {
+    /// The client logs of every running server.
+    static ref CLIENT_LOGS: Mutex<Vec<Weak<ClientLog>>> = Mutex::new(Vec::new());
+}
+
+/// Sends this server's own log records to its client. Errors and warnings
+/// are shown with `window/logMessage`; other records are sent as
+/// `$/logTrace` when the client turned tracing on with `$/setTrace`, info
+/// for `messages` and everything for `verbose`.
+#[derive(Debug)]
+pub struct ClientLog {
+    trace: Mutex<TraceValue>,
+    sender: UnboundedSender<(Level, String)>,
+    receiver: Mutex<Option<UnboundedReceiver<(Level, String)>>>,
+}
+
+impl ClientLog {
+    /// A client log that queues records until `forward_to` is called.
+    pub fn register() -> Arc<Self> {
+        let (sender, receiver) = unbounded_channel();
+        let client_log = Arc::new(Self {
+            trace: Mutex::new(TraceValue::Off),
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+        });
+        let mut client_logs = lock_quietly(&CLIENT_LOGS);
+        client_logs.retain(|client_log| client_log.strong_count() > 0);
+        client_logs.push(Arc::downgrade(&client_log));
+        client_log
+    }
+
+    pub fn set_trace(&self, trace: TraceValue) {
+        *lock_quietly(&self.trace) = trace;
+    }
+
+    /// Starts sending queued and future records to `client`.
+    pub fn forward_to(&self, client: Client) {
+        let mut receiver = match lock_quietly(&self.receiver).take() {
+            Some(receiver) => receiver,
+            None => return,
+        };
+        tokio::spawn(async move {
+            while let Some((level, message)) = receiver.recv().await {
+                match level {
+                    Level::Error => client.log_message(MessageType::ERROR, message).await,
+                    Level::Warn => client.log_message(MessageType::WARNING, message).await,
+                    _ => {
+                        client
+                            .send_notification::<LogTrace>(LogTraceParams {
+                                message,
+                                verbose: None,
+                            })
+                            .await
+                    }
+                }
+            }
+        });
+    }
+
+    fn accepts(&self, level: Level) -> bool {
+        let trace = *lock_quietly(&self.trace);
+        match level {
+            Level::Error | Level::Warn => true,
+            Level::Info => trace != TraceValue::Off,
+            Level::Debug | Level::Trace => trace == TraceValue::Verbose,
+        }
+    }
+}
+
+pub fn handle_set_trace(client_log: &ClientLog, params: &SetTraceParams) {
+    client_log.set_trace(params.value);
+}
+
+/// Hands records of this crate to every registered client log. Records of
+/// dependencies only go to the log output.
+#[derive(Debug)]
+struct ClientAppender;
+
+impl Append for ClientAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        if !record.target().starts_with("pols") {
+            return Ok(());
+        }
+        let client_logs = lock_quietly(&CLIENT_LOGS);
+        for client_log in client_logs.iter().filter_map(Weak::upgrade) {
+            if client_log.accepts(record.level()) {
+                let _ = client_log
+                    .sender
+                    .send((record.level(), record.args().to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&self) {}
+}
+
+/// Locks a mutex of the logger, recovering it after a panic. Unlike
+/// `sync::lock` it logs nothing, since logging from here would try to take
+/// the same locks again.
+fn lock_quietly<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[test]
+fn test_client_log_follows_trace() {
+    let client_log = ClientLog::register();
+    assert!(client_log.accepts(Level::Warn));
+    assert!(!client_log.accepts(Level::Info));
+
+    client_log.set_trace(TraceValue::Messages);
+    assert!(client_log.accepts(Level::Info));
+    assert!(!client_log.accepts(Level::Debug));
+
+    client_log.set_trace(TraceValue::Verbose);
+    assert!(client_log.accepts(Level::Trace));
+}
@@ -1,91 +1,481 @@
-use std::collections::HashMap;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 
-use std::process::exit;
-use std::sync::Mutex;
-
-use lazy_static::lazy_static;
-
-use tower_lsp::jsonrpc::Result;
+use log::{error, info};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tower_lsp::jsonrpc::{Error, Request, Result};
 use tower_lsp::lsp_types::*;
-use tower_lsp::{Client, LanguageServer};
-use tree_sitter::{Parser, Tree};
+use tower_lsp::{Client, ClientSocket, LanguageServer, LspService, Server};
+use tower_service::Service;
 
 use crate::handlers::handle_completion::handle_comlpetion;
+use crate::handlers::handle_completion_resolve::handle_completion_resolve;
 use crate::handlers::handle_did_change_text_document::handle_did_change_text_document;
 use crate::handlers::handle_document_symbol::handle_document_symbol;
+use crate::handlers::handle_execute_command::handle_execute_command;
+use crate::handlers::handle_file_operations::{
+    handle_did_create_files, handle_did_delete_files, handle_did_rename_files,
+};
+use crate::handlers::handle_initialize::handle_initialize;
 use crate::handlers::handle_initialized::handle_initialized;
+use crate::handlers::handle_shutdown::handle_shutdown;
+use crate::handlers::handle_signature_help::handle_signature_help;
 use crate::handlers::{handle_definition, handle_hover::handle_hover};
-use crate::{handlers::handle_initialize::handle_initialize, parser::get_parser};
+use crate::state::{ServerState, Snapshot};
+use crate::{
+    cancellation::CancellationToken,
+    diagnostics::debounce::DiagnosticsScheduler,
+    index::get_affected_files,
+    logging::{handle_set_trace, ClientLog},
+    panics::report_panic,
+};
 
 pub struct Backend {
     pub client: Client,
+    pub state: Arc<ServerState>,
+    diagnostics: Arc<DiagnosticsScheduler>,
+    client_log: Arc<ClientLog>,
 }
 
-pub struct Context {
-    pub documents: Mutex<HashMap<String, TextDocumentItem>>,
-    pub parser: Mutex<Parser>,
-    pub trees: Mutex<HashMap<String, Tree>>,
+impl Backend {
+    pub fn new(client: Client, state: Arc<ServerState>) -> Self {
+        Self {
+            client,
+            state,
+            diagnostics: Arc::new(DiagnosticsScheduler::new()),
+            client_log: ClientLog::register(),
+        }
+    }
+
+    /// `$/setTrace`, which tower-lsp leaves to the server. Registered as a
+    /// custom method when the service is built.
+    pub async fn set_trace(&self, params: SetTraceParams) {
+        handle_set_trace(&self.client_log, &params);
+    }
+
+    fn publish_affected_diagnostics(&self, affected_files: Vec<String>) {
+        self.diagnostics
+            .schedule(&self.client, &self.state, affected_files);
+    }
+
+    /// Runs `handler` on a blocking thread against the current snapshot,
+    /// within the time budget configured for `method`. The handler's token
+    /// is cancelled when the budget runs out or when the client sends
+    /// `$/cancelRequest`, which drops this future.
+    async fn run_request<T: Send + 'static>(
+        &self,
+        method: &'static str,
+        handler: impl FnOnce(&Snapshot, &CancellationToken) -> Option<T> + Send + 'static,
+    ) -> Result<Option<T>> {
+        let snapshot = self.state.snapshot();
+        let timeout = snapshot.settings.requests.timeout(method);
+        let token = self.state.shutdown.child();
+        let _guard = token.drop_guard();
+
+        let task_token = token.clone();
+        let task = tokio::task::spawn_blocking(move || handler(&snapshot, &task_token));
+        match tokio::time::timeout(timeout, task).await {
+            Ok(Ok(_)) if token.is_cancelled() => Err(Error::request_cancelled()),
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(e)) => {
+                match e.try_into_panic() {
+                    Ok(payload) => report_panic(&self.client, &self.state, method, payload).await,
+                    Err(e) => error!("Error handling {}: {}", method, e),
+                }
+                Err(Error::internal_error())
+            }
+            Err(_) => {
+                info!("{} timed out after {} ms", method, timeout.as_millis());
+                token.cancel();
+                Err(Error::request_cancelled())
+            }
+        }
+    }
 }
 
-lazy_static! {
-    pub static ref CONTEXT: Context = Context::new();
+impl Backend {
+    /// Runs a synchronous handler, turning a panic into a log entry instead
+    /// of taking the server down. Notifications stay in order this way,
+    /// which they would not if each ran as a task of its own.
+    async fn isolate<T>(&self, method: &'static str, handler: impl FnOnce() -> T) -> Option<T> {
+        match panic::catch_unwind(AssertUnwindSafe(handler)) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                report_panic(&self.client, &self.state, method, payload).await;
+                None
+            }
+        }
+    }
+
+    /// Runs a handler that parses or analyzes on a blocking thread, so it
+    /// does not hold up the runtime, and a panic only ends that thread.
+    async fn isolate_blocking<T: Send + 'static>(
+        &self,
+        method: &'static str,
+        handler: impl FnOnce() -> T + Send + 'static,
+    ) -> Option<T> {
+        match tokio::task::spawn_blocking(handler).await {
+            Ok(result) => Some(result),
+            Err(e) => {
+                match e.try_into_panic() {
+                    Ok(payload) => report_panic(&self.client, &self.state, method, payload).await,
+                    Err(e) => error!("Error handling {}: {}", method, e),
+                }
+                None
+            }
+        }
+    }
+
+    /// Runs an asynchronous handler as a task of its own, so a panic only
+    /// ends that task.
+    async fn isolate_task<T: Send + 'static>(
+        &self,
+        method: &'static str,
+        task: impl Future<Output = T> + Send + 'static,
+    ) -> Option<T> {
+        match tokio::spawn(task).await {
+            Ok(result) => Some(result),
+            Err(e) => {
+                match e.try_into_panic() {
+                    Ok(payload) => report_panic(&self.client, &self.state, method, payload).await,
+                    Err(e) => error!("Error handling {}: {}", method, e),
+                }
+                None
+            }
+        }
+    }
 }
 
-impl Context {
-    pub fn new() -> Self {
-        Self {
-            documents: Mutex::new(HashMap::new()),
-            parser: Mutex::new(get_parser()),
-            trees: Mutex::new(HashMap::new()),
+pub fn build_service(state: Arc<ServerState>) -> (LspService<Backend>, ClientSocket) {
+    LspService::build(|client| Backend::new(client, state))
+        .custom_method("$/setTrace", Backend::set_trace)
+        .finish()
+}
+
+/// Serves one client until it sends `exit` or closes its input.
+pub async fn serve<I, O>(input: I, output: O, state: Arc<ServerState>)
+where
+    I: AsyncRead + Unpin,
+    O: AsyncWrite,
+{
+    let (service, socket) = build_service(state);
+    let exited = Arc::new(Notify::new());
+    let service = ExitNotifier {
+        inner: service,
+        exited: exited.clone(),
+    };
+    tokio::select! {
+        _ = Server::new(input, output, socket).serve(service) => {}
+        _ = exited.notified() => {}
+    }
+}
+
+/// Serves a single client over stdin and stdout. As the specification asks,
+/// the exit code is 0 only when the client sent `shutdown` before `exit`.
+pub async fn serve_stdio() -> ExitCode {
+    let state = Arc::new(ServerState::new());
+    serve(tokio::io::stdin(), tokio::io::stdout(), state.clone()).await;
+    if state.shutdown.is_cancelled() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Serves every client that connects to `port` on the loopback interface.
+/// Each connection gets a server of its own, with its own state.
+pub async fn serve_tcp(port: u16) -> std::io::Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    info!("Listening on {}", listener.local_addr()?);
+    loop {
+        let (stream, address) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                error!("Error accepting connection: {}", e);
+                continue;
+            }
+        };
+        info!("Client connected from {}", address);
+        tokio::spawn(async move {
+            let (read, write) = tokio::io::split(stream);
+            serve(read, write, Arc::new(ServerState::new())).await;
+            info!("Client {} disconnected", address);
+        });
+    }
+}
+
+/// Signals when the client sends `exit`. tower-lsp stops reading only once
+/// the input ends, which a client may not close after `exit`.
+struct ExitNotifier<S> {
+    inner: S,
+    exited: Arc<Notify>,
+}
+
+impl<S: Service<Request>> Service<Request> for ExitNotifier<S> {
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> S::Future {
+        let is_exit = request.method() == "exit";
+        let future = self.inner.call(request);
+        if is_exit {
+            self.exited.notify_one();
         }
+        future
     }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
-        let result = handle_initialize();
-        Ok(result)
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        if let Some(trace) = params.trace {
+            self.client_log.set_trace(trace);
+        }
+        self.isolate("initialize", || handle_initialize(&self.state, &params))
+            .await
+            .ok_or_else(Error::internal_error)
     }
 
     async fn initialized(&self, _: InitializedParams) {
-        handle_initialized(&self.client).await;
+        self.client_log.forward_to(self.client.clone());
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let diagnostics = self.diagnostics.clone();
+        self.isolate_task("initialized", async move {
+            handle_initialized(&client, &state, &diagnostics).await
+        })
+        .await;
         self.client
             .log_message(MessageType::INFO, "server initialized!")
             .await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        handle_did_change_text_document(&params);
+        let state = self.state.clone();
+        let affected_files = self
+            .isolate_blocking("textDocument/didChange", move || {
+                handle_did_change_text_document(&state, &params);
+                let snapshot = state.snapshot();
+                get_affected_files(&snapshot, params.text_document.uri.as_str())
+            })
+            .await;
+        if let Some(affected_files) = affected_files {
+            self.publish_affected_diagnostics(affected_files);
+        }
+    }
+
+    async fn did_create_files(&self, params: CreateFilesParams) {
+        let state = self.state.clone();
+        let affected_files = self
+            .isolate_task("workspace/didCreateFiles", async move {
+                handle_did_create_files(&state, &params).await
+            })
+            .await;
+        if let Some(affected_files) = affected_files {
+            self.publish_affected_diagnostics(affected_files);
+        }
+    }
+
+    async fn did_rename_files(&self, params: RenameFilesParams) {
+        let state = self.state.clone();
+        let affected_files = self
+            .isolate_task("workspace/didRenameFiles", async move {
+                handle_did_rename_files(&state, &params).await
+            })
+            .await;
+        if let Some(affected_files) = affected_files {
+            self.publish_affected_diagnostics(affected_files);
+        }
+    }
+
+    async fn did_delete_files(&self, params: DeleteFilesParams) {
+        let affected_files = self
+            .isolate("workspace/didDeleteFiles", || {
+                handle_did_delete_files(&self.state, &params)
+            })
+            .await;
+        if let Some(affected_files) = affected_files {
+            self.publish_affected_diagnostics(affected_files);
+        }
     }
 
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
     ) -> Result<Option<DocumentSymbolResponse>> {
-        let result = handle_document_symbol(&params);
-        Ok(result)
+        self.run_request("textDocument/documentSymbol", move |snapshot, token| {
+            handle_document_symbol(snapshot, &params, token)
+        })
+        .await
     }
 
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
-        let res = handle_hover(&params);
-        Ok(res)
+        self.run_request("textDocument/hover", move |snapshot, token| {
+            handle_hover(snapshot, &params, token)
+        })
+        .await
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        self.run_request("textDocument/signatureHelp", move |snapshot, _| {
+            handle_signature_help(snapshot, &params)
+        })
+        .await
     }
 
     async fn shutdown(&self) -> Result<()> {
-        exit(0)
+        self.isolate("shutdown", || handle_shutdown(&self.state))
+            .await
+            .ok_or_else(Error::internal_error)
     }
 
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
-        let completions = handle_comlpetion(&params);
-        Ok(completions)
+        self.run_request("textDocument/completion", move |snapshot, token| {
+            handle_comlpetion(snapshot, &params, token)
+        })
+        .await
+    }
+
+    async fn completion_resolve(&self, item: CompletionItem) -> Result<CompletionItem> {
+        self.isolate("completionItem/resolve", || handle_completion_resolve(item))
+            .await
+            .ok_or_else(Error::internal_error)
+    }
+
+    async fn execute_command(&self, params: ExecuteCommandParams) -> Result<Option<LSPAny>> {
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let diagnostics = self.diagnostics.clone();
+        self.isolate_task("workspace/executeCommand", async move {
+            handle_execute_command(&client, &state, &diagnostics, &params).await
+        })
+        .await
+        .ok_or_else(Error::internal_error)
     }
 
     async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
     ) -> Result<Option<GotoDefinitionResponse>> {
-        let result = handle_definition::handle_definition(&params);
-        Ok(result)
+        self.run_request("textDocument/definition", move |snapshot, token| {
+            handle_definition::handle_definition(snapshot, &params, token)
+        })
+        .await
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_and_exit() {
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    use crate::index::cache::IndexCache;
+
+    async fn send(stream: &mut (impl AsyncWriteExt + Unpin), message: Value) {
+        let body = message.to_string();
+        let frame = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        stream.write_all(frame.as_bytes()).await.unwrap();
+    }
+    async fn receive(stream: &mut (impl AsyncBufReadExt + Unpin)) -> Value {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            stream.read_line(&mut line).await.unwrap();
+            match line.trim().strip_prefix("Content-Length: ") {
+                Some(value) => length = value.parse().unwrap(),
+                None if line.trim().is_empty() => break,
+                None => {}
+            }
+        }
+        let mut body = vec![0; length];
+        stream.read_exact(&mut body).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
     }
+
+    let cache_path =
+        std::env::temp_dir().join(format!("pols-shutdown-{}.json", std::process::id()));
+    let state = Arc::new(ServerState::new());
+    *state.index_cache.lock().unwrap() = IndexCache::new(Some(cache_path.clone()));
+
+    let (client_stream, server_stream) = tokio::io::duplex(64 * 1024);
+    let (server_read, server_write) = tokio::io::split(server_stream);
+    let server = tokio::spawn(serve(server_read, server_write, state.clone()));
+    let (client_read, mut client_write) = tokio::io::split(client_stream);
+    let mut client_read = BufReader::new(client_read);
+
+    send(
+        &mut client_write,
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}}),
+    )
+    .await;
+    assert!(receive(&mut client_read).await["result"]["capabilities"].is_object());
+
+    send(
+        &mut client_write,
+        json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+    )
+    .await;
+    assert_eq!(receive(&mut client_read).await["result"], Value::Null);
+    assert!(state.shutdown.is_cancelled());
+    assert!(cache_path.exists());
+
+    // Requests after shutdown are rejected.
+    let hover = json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "textDocument/hover",
+        "params": {"textDocument": {"uri": "file:///specs/A"}, "position": {"line": 0, "character": 0}}
+    });
+    send(&mut client_write, hover).await;
+    assert_eq!(receive(&mut client_read).await["error"]["code"], -32600);
+
+    send(
+        &mut client_write,
+        json!({"jsonrpc": "2.0", "method": "exit"}),
+    )
+    .await;
+    tokio::time::timeout(std::time::Duration::from_secs(5), server)
+        .await
+        .expect("server stops on exit")
+        .unwrap();
+
+    std::fs::remove_file(cache_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_panics_are_isolated() {
+    use std::sync::atomic::Ordering;
+
+    let state = Arc::new(ServerState::new());
+    let (service, _) = build_service(state.clone());
+    let backend = service.inner();
+
+    assert_eq!(backend.isolate("test", || 1).await, Some(1));
+    assert_eq!(
+        backend.isolate("test", || -> i32 { panic!("sync") }).await,
+        None
+    );
+    assert_eq!(
+        backend
+            .isolate_task("test", async { panic!("async") as i32 })
+            .await,
+        None
+    );
+    assert!(state.degraded.load(Ordering::Relaxed));
+
+    // An edit that panics is dropped, and later edits still go through.
+    let _ = panic::catch_unwind(AssertUnwindSafe(|| state.edit(|_| panic!("edit"))));
+    assert_eq!(state.snapshot().revision, 0);
+    state.edit(|_| ());
+    assert_eq!(state.snapshot().revision, 1);
 }
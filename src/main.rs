@@ -1,19 +1,71 @@
-use log4rs;
-use pols::lsp::Backend;
-use tower_lsp::{LspService, Server};
+use std::{
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+};
 
-#[tokio::main]
-async fn main() {
-    log4rs::init_file(
-        "/home/phil/projects/pols/logging_config.yaml",
-        Default::default(),
-    )
-    .unwrap();
+use clap::ArgMatches;
+use pols::{
+    cli::{build_cli, get_log_settings, run_parse, run_symbols},
+    logging::init_logging,
+    lsp::{serve_stdio, serve_tcp},
+    panics::install_panic_hook,
+    utils::MyResult,
+};
+use tokio::runtime::Runtime;
 
-    let stdin = tokio::io::stdin();
-    let stdout = tokio::io::stdout();
+fn main() -> ExitCode {
+    let matches = build_cli().get_matches();
+    init_logging(&get_log_settings(&matches));
+    install_panic_hook();
 
-    let (service, socket) = LspService::new(|client| Backend { client });
+    let runtime = match Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("pols: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let exit_code = runtime.block_on(run(&matches));
+    // Reading stdin blocks a runtime thread until the client closes it, which
+    // it need not do after `exit`, so the runtime is not waited for.
+    runtime.shutdown_background();
+    log::logger().flush();
+    exit_code
+}
+
+async fn run(matches: &ArgMatches) -> ExitCode {
+    let output: MyResult<String> = match matches.subcommand() {
+        Some(("parse", args)) => {
+            let file = args.get_one::<String>("file").expect("file is required");
+            let format = args
+                .get_one::<String>("format")
+                .expect("format has a default");
+            run_parse(Path::new(file), format)
+        }
+        Some(("symbols", args)) => {
+            let file = args.get_one::<String>("file").expect("file is required");
+            run_symbols(Path::new(file))
+        }
+        Some(("serve", args)) => match args.get_one::<u16>("listen") {
+            Some(port) => match serve_tcp(*port).await {
+                Ok(()) => return ExitCode::SUCCESS,
+                Err(e) => Err(e.into()),
+            },
+            None => return serve_stdio().await,
+        },
+        _ => return serve_stdio().await,
+    };
 
-    Server::new(stdin, stdout, socket).serve(service).await;
+    match output {
+        Ok(output) => {
+            // A closed pipe, as in `pols parse FILE | head`, is not an error.
+            let _ = writeln!(io::stdout(), "{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("pols: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,59 @@
+use std::{any::Any, panic, sync::atomic::Ordering};
+
+use log::error;
+use tower_lsp::{lsp_types::MessageType, Client};
+
+use crate::state::ServerState;
+
+/// Logs panics with their location, so they reach the log file and the
+/// client instead of only stderr.
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| error!("{}", info)));
+}
+
+/// The message a panic was raised with.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    "unknown panic".to_string()
+}
+
+/// Records a panic caught while handling `method`. The first one also tells
+/// the user, since results may be incomplete from then on.
+pub async fn report_panic(
+    client: &Client,
+    state: &ServerState,
+    method: &str,
+    payload: Box<dyn Any + Send>,
+) {
+    error!(
+        "Panic while handling {}: {}",
+        method,
+        panic_message(payload.as_ref())
+    );
+    if state.degraded.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    client
+        .show_message(
+            MessageType::WARNING,
+            format!(
+                "PowerOn analysis is degraded: an internal error occurred while handling {}. \
+                 Some results may be missing; see the server log for details.",
+                method
+            ),
+        )
+        .await;
+}
+
+#[test]
+fn test_panic_message() {
+    let payload = panic::catch_unwind(|| panic!("bad node {}", 3)).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "bad node 3");
+    let payload = panic::catch_unwind(|| panic!("bad node")).unwrap_err();
+    assert_eq!(panic_message(payload.as_ref()), "bad node");
+}
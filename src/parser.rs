@@ -1,4 +1,10 @@
-use tree_sitter::Parser;
+use std::cell::RefCell;
+
+use tree_sitter::{Parser, Tree};
+
+thread_local! {
+    static PARSER: RefCell<Parser> = RefCell::new(get_parser());
+}
 
 pub fn get_parser() -> Parser {
     let mut parser = Parser::new();
@@ -8,6 +14,12 @@ pub fn get_parser() -> Parser {
     parser
 }
 
+/// Parses `source` with this thread's parser, so parsing on several threads
+/// at once never contends on a shared parser.
+pub fn parse(source: &str) -> Option<Tree> {
+    PARSER.with(|parser| parser.borrow_mut().parse(source, None))
+}
+
 #[test]
 fn test_parser() {
     let mut parser = get_parser();
@@ -0,0 +1,114 @@
+use std::{collections::HashMap, time::Duration};
+
+use log::error;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::text::Encoding;
+
+/// Server settings, read from the `initializationOptions` the client sends
+/// with `initialize`. Missing settings keep their defaults.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub files: FileSettings,
+    pub requests: RequestSettings,
+    pub diagnostics: DiagnosticSettings,
+}
+
+/// Which workspace files are indexed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FileSettings {
+    /// Globs, relative to the workspace folder, of the files to index. When
+    /// empty every file that is not excluded is indexed.
+    pub include: Vec<String>,
+    /// Globs of files and folders never to index, on top of `.gitignore`.
+    pub exclude: Vec<String>,
+    /// Files larger than this many bytes are not indexed.
+    pub max_file_size: u64,
+    /// How files are decoded: `auto`, `utf-8`, `windows-1252` or `latin1`.
+    pub encoding: Encoding,
+}
+
+impl Default for FileSettings {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            max_file_size: 1024 * 1024,
+            encoding: Encoding::Auto,
+        }
+    }
+}
+
+/// How long requests may run before they are abandoned.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RequestSettings {
+    /// The time budget of a request, in milliseconds.
+    pub timeout: u64,
+    /// Budgets for single methods, keyed by method name such as
+    /// `textDocument/hover`, overriding `timeout`.
+    pub timeouts: HashMap<String, u64>,
+}
+
+impl Default for RequestSettings {
+    fn default() -> Self {
+        Self {
+            timeout: 5000,
+            timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl RequestSettings {
+    pub fn timeout(&self, method: &str) -> Duration {
+        let timeout = self.timeouts.get(method).copied().unwrap_or(self.timeout);
+        Duration::from_millis(timeout)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiagnosticSettings {
+    /// How long to wait after an edit before publishing diagnostics, in
+    /// milliseconds. Edits made while waiting restart the wait.
+    pub debounce: u64,
+}
+
+impl Default for DiagnosticSettings {
+    fn default() -> Self {
+        Self { debounce: 250 }
+    }
+}
+
+pub fn get_settings(initialization_options: Option<&Value>) -> Settings {
+    let options = match initialization_options {
+        Some(options) if !options.is_null() => options,
+        _ => return Settings::default(),
+    };
+    match serde_json::from_value(options.clone()) {
+        Ok(settings) => settings,
+        Err(e) => {
+            error!("Error reading initialization options: {}", e);
+            Settings::default()
+        }
+    }
+}
+
+#[test]
+fn test_request_timeouts() {
+    let settings = get_settings(Some(&serde_json::json!({
+        "requests": { "timeouts": { "textDocument/hover": 100 } }
+    })));
+    assert_eq!(
+        settings.requests.timeout("textDocument/hover"),
+        Duration::from_millis(100)
+    );
+    assert_eq!(
+        settings.requests.timeout("textDocument/definition"),
+        Duration::from_millis(5000)
+    );
+    assert_eq!(settings.diagnostics.debounce, 250);
+}
@@ -0,0 +1,166 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc, Mutex, OnceLock, RwLock},
+};
+
+use log::error;
+use tower_lsp::lsp_types::TextDocumentItem;
+use tree_sitter::Tree;
+
+use crate::{
+    cancellation::CancellationToken,
+    index::{
+        analysis::AnalysisDatabase, cache::IndexCache, file_kind::FileKind,
+        include_graph::IncludeGraph, symbols::FileSymbols,
+    },
+    parser::parse,
+    settings::Settings,
+    sync::{lock, read, write},
+};
+
+/// A document and its syntax tree. The tree is parsed on first use, so
+/// documents restored from the index cache are only parsed once a request
+/// needs them, and then only once for every snapshot that shares them.
+#[derive(Debug)]
+pub struct Document {
+    pub item: TextDocumentItem,
+    tree: OnceLock<Option<Tree>>,
+}
+
+impl Document {
+    pub fn new(item: TextDocumentItem) -> Self {
+        Self {
+            item,
+            tree: OnceLock::new(),
+        }
+    }
+
+    /// A document that is parsed right away, so the work is not done later
+    /// while the workspace state is being updated.
+    pub fn parsed(item: TextDocumentItem) -> Self {
+        let document = Self::new(item);
+        document.tree();
+        document
+    }
+
+    pub fn tree(&self) -> Option<&Tree> {
+        self.tree
+            .get_or_init(|| {
+                let tree = parse(&self.item.text);
+                if tree.is_none() {
+                    error!("Error parsing {}", self.item.uri);
+                }
+                tree
+            })
+            .as_ref()
+    }
+}
+
+/// An immutable view of the workspace. Snapshots share their contents with
+/// the server state, so taking one is a handful of reference count bumps,
+/// and a request holding one never blocks or sees a half-applied edit.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub documents: Arc<HashMap<String, Arc<Document>>>,
+    pub include_graph: Arc<IncludeGraph>,
+    pub symbols: Arc<HashMap<String, Arc<FileSymbols>>>,
+    pub file_kinds: Arc<HashMap<String, FileKind>>,
+    /// The driver chosen as analysis context for an include file.
+    pub active_drivers: Arc<HashMap<String, String>>,
+    pub workspace_folders: Arc<Vec<PathBuf>>,
+    pub settings: Arc<Settings>,
+    /// Counts edits. Every edit gets the next revision.
+    pub revision: u64,
+    /// The revision of the last edit to each file.
+    pub file_revisions: Arc<HashMap<String, u64>>,
+    /// The revision of the last edit that changed how includes resolve.
+    pub graph_revision: u64,
+    /// Memoized analysis, shared by all snapshots rather than copied.
+    pub analysis: Arc<AnalysisDatabase>,
+}
+
+impl Snapshot {
+    pub fn document(&self, uri: &str) -> Option<Arc<Document>> {
+        self.documents.get(uri).cloned()
+    }
+
+    pub fn tree(&self, uri: &str) -> Option<Tree> {
+        self.documents.get(uri)?.tree().cloned()
+    }
+
+    pub fn file_revision(&self, uri: &str) -> u64 {
+        self.file_revisions.get(uri).copied().unwrap_or_default()
+    }
+}
+
+/// The state of one language server. Each `Backend` owns its own, so several
+/// servers can run in one process.
+#[derive(Debug)]
+pub struct ServerState {
+    current: RwLock<Snapshot>,
+    /// Serializes edits. Each edit works on a copy of the current snapshot
+    /// and swaps it in when done, so readers only wait for the swap.
+    edit_lock: Mutex<()>,
+    pub index_cache: Mutex<IndexCache>,
+    /// Cancelled when the client asks the server to shut down. Requests and
+    /// background work run with tokens derived from it.
+    pub shutdown: CancellationToken,
+    /// Set once a handler panicked, after which results may be incomplete.
+    pub degraded: AtomicBool,
+}
+
+impl ServerState {
+    pub fn new() -> Self {
+        Self {
+            current: RwLock::new(Snapshot::default()),
+            edit_lock: Mutex::new(()),
+            index_cache: Mutex::new(IndexCache::new(None)),
+            shutdown: CancellationToken::new(),
+            degraded: AtomicBool::new(false),
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        read(&self.current).clone()
+    }
+
+    /// Applies `edit` to a copy of the current snapshot and publishes the
+    /// result. Maps are copied on write by `Arc::make_mut`, which only copies
+    /// the maps the edit touches. An edit that panics is never published, so
+    /// the current snapshot stays consistent.
+    pub fn edit<R>(&self, edit: impl FnOnce(&mut Snapshot) -> R) -> R {
+        let _edit_guard = lock(&self.edit_lock);
+        let mut next = self.snapshot();
+        next.revision += 1;
+        let result = edit(&mut next);
+        *write(&self.current) = next;
+        result
+    }
+}
+
+impl Default for ServerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_server_states_are_independent() {
+    use crate::test_utils::document;
+
+    let first = ServerState::new();
+    let second = ServerState::new();
+    let before = first.snapshot();
+    first.edit(|snapshot| {
+        Arc::make_mut(&mut snapshot.documents).insert(
+            "file:///specs/A.DEF".to_string(),
+            Arc::new(document("file:///specs/A.DEF", "TRUE=1")),
+        );
+    });
+
+    let after = first.snapshot();
+    assert!(after.tree("file:///specs/A.DEF").is_some());
+    assert!(before.document("file:///specs/A.DEF").is_none());
+    assert!(second.snapshot().document("file:///specs/A.DEF").is_none());
+}
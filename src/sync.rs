@@ -0,0 +1,64 @@
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use log::error;
+
+/// Locks `mutex`, recovering it when a thread panicked while holding it,
+/// so one failed request does not lock every later request out.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    lock_or_reset(mutex, |_| {})
+}
+
+/// Like `lock`, but the value of a poisoned mutex is handed to `reset`
+/// first, for data a panic may have left half-updated.
+pub fn lock_or_reset<T>(mutex: &Mutex<T>, reset: impl FnOnce(&mut T)) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            error!("Recovering a lock poisoned by a panic");
+            let mut guard = poisoned.into_inner();
+            reset(&mut guard);
+            mutex.clear_poison();
+            guard
+        }
+    }
+}
+
+pub fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    match lock.read() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            error!("Recovering a lock poisoned by a panic");
+            lock.clear_poison();
+            poisoned.into_inner()
+        }
+    }
+}
+
+pub fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    match lock.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            error!("Recovering a lock poisoned by a panic");
+            lock.clear_poison();
+            poisoned.into_inner()
+        }
+    }
+}
+
+#[test]
+fn test_poisoned_lock_is_recovered() {
+    let mutex = Mutex::new(vec![1, 2]);
+    let _ = std::panic::catch_unwind(|| {
+        let _guard = mutex.lock().unwrap();
+        panic!("poison");
+    });
+    assert!(mutex.is_poisoned());
+
+    assert_eq!(
+        *lock_or_reset(&mutex, |values| values.clear()),
+        Vec::<i32>::new()
+    );
+    assert!(!mutex.is_poisoned());
+    lock(&mutex).push(3);
+    assert_eq!(*lock(&mutex), vec![3]);
+}
@@ -0,0 +1,51 @@
+//! Fixtures shared by the tests of every module.
+
+use std::sync::Arc;
+
+use tower_lsp::lsp_types::{Diagnostic, TextDocumentItem, Url};
+
+use crate::{
+    cancellation::CancellationToken,
+    index::{analysis::Scope, index_document},
+    state::{Document, Snapshot},
+};
+
+pub fn document_item(uri: &str, text: &str) -> TextDocumentItem {
+    TextDocumentItem {
+        uri: Url::parse(uri).unwrap(),
+        language_id: "poweron".to_string(),
+        version: 0,
+        text: text.to_string(),
+    }
+}
+
+/// A parsed document of `text` at `uri`.
+pub fn document(uri: &str, text: &str) -> Document {
+    Document::parsed(document_item(uri, text))
+}
+
+/// A snapshot with each of `files`, given as a uri and its text, indexed.
+pub fn snapshot_with(files: &[(&str, &str)]) -> Snapshot {
+    let mut snapshot = Snapshot::default();
+    for (uri, text) in files {
+        index_document(&mut snapshot, document(uri, text));
+    }
+    snapshot
+}
+
+/// The declarations visible from `uri` in `snapshot`.
+pub fn scope_of(snapshot: &Snapshot, uri: &str) -> Arc<Scope> {
+    snapshot
+        .analysis
+        .scope(snapshot, uri, &CancellationToken::new())
+        .unwrap()
+}
+
+/// The line and message of each of `diagnostics`, for comparing them with
+/// what a test expects.
+pub fn diagnostic_lines(diagnostics: &[Diagnostic]) -> Vec<(u32, &str)> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.range.start.line, diagnostic.message.as_str()))
+        .collect()
+}
@@ -0,0 +1,130 @@
+use serde::Deserialize;
+use tower_lsp::lsp_types::Position;
+use tree_sitter::Point;
+
+/// How specfiles are decoded when they are read from disk. Files pulled from
+/// the host are often Windows-1252 rather than UTF-8.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum Encoding {
+    /// UTF-8 when the file is valid UTF-8, Windows-1252 otherwise.
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "utf-8", alias = "utf8")]
+    Utf8,
+    #[serde(rename = "windows-1252", alias = "cp1252")]
+    Windows1252,
+    #[serde(rename = "latin1", alias = "iso-8859-1")]
+    Latin1,
+}
+
+/// The characters Windows-1252 puts at 0x80-0x9F. The five bytes it leaves
+/// undefined map to the C1 control of the same value, so every byte decodes
+/// to a character.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// Decodes `bytes`. A UTF-8 byte order mark is dropped, as editors hide it.
+pub fn decode(bytes: &[u8], encoding: Encoding) -> String {
+    let encoding = match encoding {
+        Encoding::Auto if std::str::from_utf8(bytes).is_ok() => Encoding::Utf8,
+        Encoding::Auto => Encoding::Windows1252,
+        encoding => encoding,
+    };
+    match encoding {
+        Encoding::Utf8 => {
+            let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+        Encoding::Windows1252 => bytes.iter().map(|&b| windows_1252_char(b)).collect(),
+        Encoding::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+        // Resolved to a concrete encoding above.
+        Encoding::Auto => unreachable!("Auto is resolved before decoding"),
+    }
+}
+
+fn windows_1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+/// Converts a client position, whose character counts UTF-16 code units, to
+/// a tree-sitter point, whose column counts bytes. Positions past the end of
+/// a line are clamped to it.
+pub fn position_to_point(text: &str, position: Position) -> Point {
+    let row = position.line as usize;
+    let line = match text.split('\n').nth(row) {
+        Some(line) => line.trim_end_matches('\r'),
+        None => return Point::new(row, 0),
+    };
+    let mut units = 0;
+    for (column, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return Point::new(row, column);
+        }
+        units += c.len_utf16();
+    }
+    Point::new(row, line.len())
+}
+
+/// The byte offset of a tree-sitter point in `text`.
+pub fn point_to_byte(text: &str, point: Point) -> usize {
+    let line_start: usize = text
+        .split_inclusive('\n')
+        .take(point.row)
+        .map(|line| line.len())
+        .sum();
+    (line_start + point.column).min(text.len())
+}
+
+/// Converts a tree-sitter point at `byte` in `text` to a client position.
+pub fn point_to_position(text: &str, point: Point, byte: usize) -> Position {
+    let line_start = byte.saturating_sub(point.column);
+    let character = match text.get(line_start..byte) {
+        Some(prefix) => prefix.encode_utf16().count(),
+        None => point.column,
+    };
+    Position {
+        line: point.row as u32,
+        character: character as u32,
+    }
+}
+
+#[test]
+fn test_decode() {
+    let bytes = b"PRINT TITLE=\"Jos\xE9 \x80 \x81\"\r\n";
+    assert_eq!(
+        decode(bytes, Encoding::Auto),
+        "PRINT TITLE=\"José € \u{81}\"\r\n"
+    );
+    assert_eq!(
+        decode(bytes, Encoding::Latin1),
+        "PRINT TITLE=\"José \u{80} \u{81}\"\r\n"
+    );
+    assert_eq!(decode("\u{FEFF}José".as_bytes(), Encoding::Auto), "José");
+}
+
+#[test]
+fn test_position_conversion() {
+    // "é" is two bytes and one UTF-16 unit, "𝄞" four bytes and two units.
+    let text = "TARGET=ACCOUNT\r\nPRINT \"é𝄞\" X\r\n";
+    let x = text.rfind('X').unwrap();
+    let point = Point::new(1, x - text.find("PRINT").unwrap());
+    let position = Position {
+        line: 1,
+        character: 12,
+    };
+    assert_eq!(point_to_position(text, point, x), position);
+    assert_eq!(position_to_point(text, position), point);
+    let end_of_line = Position {
+        line: 1,
+        character: 40,
+    };
+    assert_eq!(position_to_point(text, end_of_line), Point::new(1, 16));
+}